@@ -0,0 +1,166 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::{self, Display},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Global allocator that counts allocations, opt in with
+///
+/// ```no_run
+/// #[global_allocator]
+/// static ALLOCATOR: advent_utils::CountingAllocator = advent_utils::CountingAllocator;
+/// # fn main() {}
+/// ```
+///
+/// Counts are kept per thread, so puzzles running in parallel don't see each other's
+/// allocations. Memory freed on a different thread than it was allocated on isn't subtracted
+/// from the allocating thread's live bytes
+pub struct CountingAllocator;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static STATS: Cell<AllocStats> = const { Cell::new(AllocStats::new()) };
+    static LIVE: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Allocations made while solving a puzzle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub count: u64,
+    /// Bytes allocated in total, including memory that was freed again
+    pub bytes: u64,
+    /// Most bytes allocated at the same time
+    pub peak: u64,
+}
+
+impl AllocStats {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            bytes: 0,
+            peak: 0,
+        }
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} total, {} peak",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Records `size` new bytes and `freed` released bytes on this thread
+fn record(size: u64, freed: u64) {
+    // try_with as the allocator can still be called while the thread is shutting down
+    let _ = LIVE.try_with(|live| {
+        let current = (live.get() + size).saturating_sub(freed);
+        live.set(current);
+        let _ = STATS.try_with(|stats| {
+            let mut updated = stats.get();
+            if size > 0 {
+                updated.count += 1;
+                updated.bytes += size;
+            }
+            updated.peak = updated.peak.max(current);
+            stats.set(updated);
+        });
+    });
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size() as u64, 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size() as u64);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size() as u64, 0);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size as u64, layout.size() as u64);
+        }
+        new
+    }
+}
+
+/// Runs `f`, counting what it allocates on this thread. The stats are `None` unless
+/// `CountingAllocator` is the global allocator
+pub(crate) fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    let live = LIVE.get();
+    STATS.set(AllocStats {
+        peak: live,
+        ..AllocStats::new()
+    });
+    let result = f();
+    let stats = STATS.get();
+    let stats = INSTALLED.load(Ordering::Relaxed).then_some(AllocStats {
+        // only what was allocated on top of what the thread already held
+        peak: stats.peak - live,
+        ..stats
+    });
+    (result, stats)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_bytes, measure, CountingAllocator};
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn counts() {
+        let (_, stats) = measure(|| {
+            let small = vec![0u8; 100];
+            drop(small);
+            let large = vec![0u8; 1000];
+            large.len()
+        });
+        let stats = stats.unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.bytes, 1100);
+        assert_eq!(stats.peak, 1000);
+        assert_eq!(format_bytes(1000), "1000 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{parser, InputSource, TestRunner, Timeouts, DEFAULT_YEAR};
+
+/// Known correct answers for one year keyed by (day, part)
+///
+/// Stored as plain text, one `day part answer` entry per line. Blank lines and lines starting
+/// with `#` are ignored. Answers spanning several lines are stored with [`escape`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+impl Answers {
+    /// `./answers.txt` for the default year, `./answers_<year>.txt` for any other
+    pub fn default_path(year: u16) -> PathBuf {
+        match year {
+            DEFAULT_YEAR => PathBuf::from("./answers.txt"),
+            year => PathBuf::from(format!("./answers_{year}.txt")),
+        }
+    }
+
+    /// Reads the answers file, a missing file is treated as having no answers yet
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(data) => data
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Self::default();
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(format!("line {}: expected `day part answer`", number + 1));
+            };
+            let day = day
+                .parse()
+                .map_err(|e| format!("line {}: bad day {day}: {e}", number + 1))?;
+            let part = part
+                .parse()
+                .map_err(|e| format!("line {}: bad part {part}: {e}", number + 1))?;
+            answers.insert(day, part, unescape(answer.trim()));
+        }
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day part answer")?;
+        for ((day, part), answer) in &self.answers {
+            writeln!(f, "{day} {part} {}", escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps an answer on a single line of a line based file, answers spanning several lines like
+/// rendered letters have their line breaks written as `\n` and backslashes doubled
+pub(crate) fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Like [`escape`], also writing spaces as `\s` so the text stays a single field of a space
+/// separated line
+pub(crate) fn escape_field(text: &str) -> String {
+    escape(text).replace(' ', "\\s")
+}
+
+/// Reverses [`escape`] and [`escape_field`]
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut unescaped = String::with_capacity(answer.len());
+    let mut chars = answer.chars().peekable();
+    while let Some(c) = chars.next() {
+        let escaped = match (c, chars.peek()) {
+            ('\\', Some('n')) => '\n',
+            ('\\', Some('s')) => ' ',
+            ('\\', Some('\\')) => '\\',
+            _ => {
+                unescaped.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        unescaped.push(escaped);
+    }
+    unescaped
+}
+
+impl TestRunner {
+    /// Checks every registered puzzle against the answers file, returns false on any mismatch
+    ///
+    /// With `record` set the current answers are written back to the file instead
+    pub fn verify(
+        year: u16,
+        path: impl AsRef<Path>,
+        record: bool,
+        timeouts: &Timeouts,
+    ) -> io::Result<bool> {
+        let path = path.as_ref();
+        let mut answers = Answers::load(path)?;
+        let (mut passed, mut failed, mut missing) = (0, 0, 0);
+        let tests = Self::sorted(year);
+        let outcomes = parser::groups(&tests)
+            .into_iter()
+            .flat_map(|group| Self::run_group(group, &InputSource::Default, timeouts));
+        for outcome in outcomes {
+            let (day, part) = (outcome.day, outcome.part);
+            let answer = match outcome.answer {
+                Ok(answer) => answer,
+                Err(error) => {
+                    println!("Day {day} Part {part}: ERROR {error}");
+                    failed += 1;
+                    continue;
+                }
+            };
+            match answers.get(day, part) {
+                _ if record => {
+                    println!("Day {day} Part {part}: RECORDED {answer}");
+                    answers.insert(day, part, answer);
+                }
+                Some(expected) if expected == answer => {
+                    println!("Day {day} Part {part}: PASS {answer}");
+                    passed += 1;
+                }
+                Some(expected) => {
+                    println!("Day {day} Part {part}: FAIL expected {expected}, got {answer}");
+                    failed += 1;
+                }
+                None => {
+                    println!("Day {day} Part {part}: MISSING {answer}");
+                    missing += 1;
+                }
+            }
+        }
+        if record {
+            answers.save(path)?;
+            println!("Wrote answers to {}", path.display());
+        } else {
+            println!("{passed} passed, {failed} failed, {missing} missing");
+        }
+        Ok(failed == 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Answers;
+
+    #[test]
+    fn round_trip() {
+        let data = "# day part answer
+        1 1 54953
+
+        5 2 46
+        7 1 some answer";
+        let answers = data.parse::<Answers>().unwrap();
+        assert_eq!(answers.get(1, 1), Some("54953"));
+        assert_eq!(answers.get(5, 2), Some("46"));
+        assert_eq!(answers.get(7, 1), Some("some answer"));
+        assert_eq!(answers.get(1, 2), None);
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+        assert!("1 x 2".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn multi_line() {
+        let mut answers = Answers::default();
+        let letters = "#..#\n####\n#..#";
+        answers.insert(10, 2, letters.to_string());
+        answers.insert(11, 1, "a\\nb".to_string());
+        let data = answers.to_string();
+        assert_eq!(data.lines().count(), 3);
+        assert!(data.contains("10 2 #..#\\n####\\n#..#"));
+        let loaded = data.parse::<Answers>().unwrap();
+        assert_eq!(loaded.get(10, 2), Some(letters));
+        assert_eq!(loaded.get(11, 1), Some("a\\nb"));
+    }
+}
@@ -0,0 +1,224 @@
+use std::time::{Duration, Instant};
+
+use crate::{variants::DEFAULT_VARIANT, InputSource, TestRunner};
+
+/// Which puzzles to benchmark and how many times to run them
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub year: u16,
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    /// Runs that are timed
+    pub runs: usize,
+    /// Runs done before timing starts, to warm up caches
+    pub warmup: usize,
+    /// Benchmark this named solution of each puzzle that has one instead of the usual one
+    pub variant: Option<String>,
+    /// Benchmark every solution of each puzzle
+    pub all_variants: bool,
+}
+
+/// Summary statistics over a set of timed runs
+#[derive(Debug, PartialEq)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    p95: Duration,
+    stddev: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let len = samples.len();
+        let mean = samples.iter().sum::<Duration>() / len as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / len as f64;
+        // nearest rank percentile
+        let p95 = samples[((len * 95).div_ceil(100)).max(1) - 1];
+        Some(Self {
+            min: samples[0],
+            median: samples[len / 2],
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+struct BenchRow {
+    day: u8,
+    part: u8,
+    variant: &'static str,
+    read: Duration,
+    solve: Result<Stats, String>,
+}
+
+impl TestRunner {
+    /// Times one solution of each selected puzzle, or the chosen variants, with reading the input
+    /// measured separately from solving it
+    pub fn bench(options: &BenchOptions) -> bool {
+        let rows = select(Self::variants(options.year), options)
+            .into_iter()
+            .map(|test| test.bench_one(options))
+            .collect::<Vec<_>>();
+        print_table(&rows);
+        rows.iter().all(|row| row.solve.is_ok())
+    }
+
+    fn bench_one(&self, options: &BenchOptions) -> BenchRow {
+        let start = Instant::now();
+        let lines = self.read_input(&InputSource::Default);
+        let read = start.elapsed();
+        let solve = lines.and_then(|lines| self.time_solve(lines, options));
+        BenchRow {
+            day: self.day,
+            part: self.part,
+            variant: self.variant.unwrap_or(DEFAULT_VARIANT),
+            read,
+            solve,
+        }
+    }
+
+    fn time_solve(&self, lines: Vec<String>, options: &BenchOptions) -> Result<Stats, String> {
+        for _ in 0..options.warmup {
+            (self.solve)(lines.clone())?;
+        }
+        let mut samples = Vec::with_capacity(options.runs);
+        for _ in 0..options.runs {
+            let lines = lines.clone();
+            let start = Instant::now();
+            (self.solve)(lines)?;
+            samples.push(start.elapsed());
+        }
+        Stats::new(samples).ok_or_else(|| "no timed runs".to_string())
+    }
+}
+
+/// Picks the solutions to benchmark out of every solution sorted by day, part then variant
+fn select<'a>(tests: Vec<&'a TestRunner>, options: &BenchOptions) -> Vec<&'a TestRunner> {
+    let mut tests = tests
+        .into_iter()
+        .filter(|test| options.day.is_none_or(|day| day == test.day))
+        .filter(|test| options.part.is_none_or(|part| part == test.part))
+        .collect::<Vec<_>>();
+    match &options.variant {
+        Some(variant) => {
+            tests.retain(|test| test.variant.unwrap_or(DEFAULT_VARIANT) == variant.as_str())
+        }
+        // the same puzzle solved several ways would otherwise dominate the total
+        None if !options.all_variants => tests.dedup_by_key(|test| (test.day, test.part)),
+        None => {}
+    }
+    tests
+}
+
+fn print_table(rows: &[BenchRow]) {
+    let total = rows
+        .iter()
+        .filter_map(|row| row.solve.as_ref().ok())
+        .map(|stats| stats.mean)
+        .sum::<Duration>();
+    println!(
+        "{:>3} {:>4} {:<10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>7}",
+        "Day", "Part", "Variant", "Read", "Min", "Median", "Mean", "P95", "StdDev", "Share"
+    );
+    for BenchRow {
+        day,
+        part,
+        variant,
+        read,
+        solve,
+    } in rows
+    {
+        let read = format!("{read:.2?}");
+        match solve {
+            Ok(stats) => {
+                let share = stats.mean.as_secs_f64() / total.as_secs_f64() * 100.0;
+                println!(
+                    "{day:>3} {part:>4} {variant:<10} {read:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {share:>6.1}%",
+                    format!("{:.2?}", stats.min),
+                    format!("{:.2?}", stats.median),
+                    format!("{:.2?}", stats.mean),
+                    format!("{:.2?}", stats.p95),
+                    format!("{:.2?}", stats.stddev),
+                );
+            }
+            Err(error) => println!("{day:>3} {part:>4} {variant:<10} {read:>10} FAILED: {error}"),
+        }
+    }
+    println!("Total mean solve time: {total:.2?}");
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{TestRunner, DEFAULT_YEAR};
+
+    use super::{select, BenchOptions, Stats};
+
+    #[test]
+    fn stats() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+        let stats = Stats::new(samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+        assert_eq!(Stats::new(vec![]), None);
+    }
+
+    #[test]
+    fn selection() {
+        let tests = [
+            TestRunner::test(5, 1, None, "day5::part_1"),
+            TestRunner::test(5, 2, None, "day5::part_2"),
+            TestRunner::test(5, 2, Some("brute"), "day5::brute"),
+            TestRunner::test(6, 1, None, "day6::part_1"),
+        ];
+        let tests = tests.iter().collect::<Vec<_>>();
+        let options = BenchOptions {
+            year: DEFAULT_YEAR,
+            day: None,
+            part: None,
+            runs: 1,
+            warmup: 0,
+            variant: None,
+            all_variants: false,
+        };
+        let sources = |options: &BenchOptions| {
+            select(tests.clone(), options)
+                .iter()
+                .map(|test| test.source)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            sources(&options),
+            ["day5::part_1", "day5::part_2", "day6::part_1"]
+        );
+        assert_eq!(
+            sources(&BenchOptions {
+                variant: Some("brute".to_string()),
+                ..options.clone()
+            }),
+            ["day5::brute"]
+        );
+        assert_eq!(
+            sources(&BenchOptions {
+                day: Some(5),
+                all_variants: true,
+                ..options.clone()
+            }),
+            ["day5::part_1", "day5::part_2", "day5::brute"]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{TestRunner, DEFAULT_PROFILE, DEFAULT_YEAR};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable holding the session cookie
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Where the session cookie, throttle state and other local state is kept
+pub const STATE_DIR: &str = "./.aoc";
+const USER_AGENT: &str = "github.com/akarras/adventofcode2023";
+
+/// Client for the advent of code website
+#[derive(Debug, Clone)]
+pub struct AocClient {
+    pub base_url: String,
+    pub session: String,
+    pub year: u16,
+    /// Minimum time between two requests, shared by every run of the binary
+    pub throttle: Duration,
+    pub state_dir: PathBuf,
+}
+
+/// What happened when fetching an input
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded(PathBuf),
+    /// The input was already on disk so nothing was requested
+    Cached(PathBuf),
+}
+
+impl AocClient {
+    /// Reads the session cookie from `AOC_SESSION`, falling back to `<state_dir>/session`
+    pub fn load_session(state_dir: &Path) -> Result<String, String> {
+        if let Ok(session) = env::var(SESSION_ENV) {
+            return Ok(session.trim().to_string());
+        }
+        let file = state_dir.join("session");
+        fs::read_to_string(&file)
+            .map(|session| session.trim().to_string())
+            .map_err(|e| {
+                format!(
+                    "no session token, set {SESSION_ENV} or write it to {}: {e}",
+                    file.display()
+                )
+            })
+    }
+
+    /// Downloads a day's input to `destination` unless it is already there
+    pub fn fetch_input(&self, day: u8, destination: &Path) -> Result<Fetched, String> {
+        if destination.is_file() {
+            return Ok(Fetched::Cached(destination.to_path_buf()));
+        }
+        let url = format!("{}/{}/day/{day}/input", self.base_url, self.year);
+        let input = self
+            .request(ureq::get(&url), &[])?
+            .into_string()
+            .map_err(|e| format!("unable to read response from {url}: {e}"))?;
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("unable to create {}: {e}", parent.display()))?;
+        }
+        fs::write(destination, input)
+            .map_err(|e| format!("unable to write {}: {e}", destination.display()))?;
+        Ok(Fetched::Downloaded(destination.to_path_buf()))
+    }
+
+    /// Sends a request with the session cookie once the throttle allows it, a non empty `form`
+    /// is sent as the body
+    pub(crate) fn request(
+        &self,
+        request: ureq::Request,
+        form: &[(&str, &str)],
+    ) -> Result<ureq::Response, String> {
+        self.wait_for_throttle();
+        let url = request.url().to_string();
+        let request = request
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT);
+        let response = if form.is_empty() {
+            request.call()
+        } else {
+            request.send_form(form)
+        };
+        self.record_request();
+        response.map_err(|e| match e {
+            ureq::Error::Status(status, response) => format!(
+                "{url} returned {status}: {}",
+                response.into_string().unwrap_or_default().trim()
+            ),
+            e => format!("unable to reach {url}: {e}"),
+        })
+    }
+
+    fn throttle_file(&self) -> PathBuf {
+        self.state_dir.join("last_request")
+    }
+
+    fn wait_for_throttle(&self) {
+        let last = fs::read_to_string(self.throttle_file())
+            .ok()
+            .and_then(|last| last.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            let next = last + self.throttle;
+            if let Some(wait) = next.checked_sub(now()) {
+                println!("Waiting {wait:.1?} before the next request");
+                thread::sleep(wait);
+            }
+        }
+    }
+
+    fn record_request(&self) {
+        // losing the throttle state only means the next request isn't delayed
+        let _ = fs::create_dir_all(&self.state_dir)
+            .and_then(|_| fs::write(self.throttle_file(), now().as_millis().to_string()));
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+impl TestRunner {
+    /// Where a day's input lives, following the registered puzzle's input file
+    ///
+    /// Days without a registered puzzle use the same layout as the `advent_of_code` attribute,
+    /// `test_data/day_N` for the default year and `test_data/<year>/day_N` for any other
+    pub fn input_path(year: u16, day: u8, profile: Option<&str>) -> PathBuf {
+        let file = inventory::iter::<TestRunner>()
+            .find(|runner| runner.year == year && runner.day == day)
+            .map(|runner| PathBuf::from(runner.file))
+            .unwrap_or_else(|| match year {
+                DEFAULT_YEAR => PathBuf::from(format!("./test_data/day_{day}")),
+                year => PathBuf::from(format!("./test_data/{year}/day_{day}")),
+            });
+        match profile {
+            Some(profile) => file.join(format!("{profile}.txt")),
+            None if file.is_dir() => file.join(format!("{DEFAULT_PROFILE}.txt")),
+            None => file,
+        }
+    }
+
+    /// Downloads the inputs for the given days, returns false if any failed
+    pub fn fetch(client: &AocClient, days: &[u8], profile: Option<&str>) -> bool {
+        let mut passed = true;
+        for &day in days {
+            match client.fetch_input(day, &Self::input_path(client.year, day, profile)) {
+                Ok(Fetched::Downloaded(path)) => {
+                    println!("Day {day}: downloaded to {}", path.display())
+                }
+                Ok(Fetched::Cached(path)) => {
+                    println!("Day {day}: already have {}", path.display())
+                }
+                Err(e) => {
+                    passed = false;
+                    println!("Day {day}: {e}");
+                }
+            }
+        }
+        passed
+    }
+}
+
+/// Serves canned responses on a local port, used to test the client without the real site
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// A request the stub received
+    #[derive(Debug)]
+    pub(crate) struct Received {
+        pub request_line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    /// Starts a server that answers each request with the next of `responses`, returns the base
+    /// url and a receiver of the requests it got
+    pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Received>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for ((status, body), stream) in responses.into_iter().zip(listener.incoming()) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut headers = vec![];
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    headers.push(header.trim().to_string());
+                }
+                let length = headers
+                    .iter()
+                    .find_map(|header| {
+                        let (name, value) = header.split_once(": ")?;
+                        name.eq_ignore_ascii_case("content-length").then_some(value)
+                    })
+                    .map_or(0, |length| length.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+                let _ = sender.send(Received {
+                    request_line: request_line.trim().to_string(),
+                    headers,
+                    body: String::from_utf8(request_body).unwrap(),
+                });
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, receiver)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use crate::TempDir;
+
+    use super::{stub, AocClient, Fetched};
+
+    #[test]
+    fn fetch_and_cache() {
+        let (base_url, requests) = stub::serve(vec![(200, "RL\n\nAAA = (ZZZ, ZZZ)\n")]);
+        let dir = TempDir::new("fetch");
+        let client = AocClient {
+            base_url,
+            session: "secret".to_string(),
+            year: 2023,
+            throttle: Duration::ZERO,
+            state_dir: dir.join(".aoc"),
+        };
+        let destination = dir.join("test_data").join("day_8");
+        assert_eq!(
+            client.fetch_input(8, &destination),
+            Ok(Fetched::Downloaded(destination.clone()))
+        );
+        let request = requests.recv().unwrap();
+        assert_eq!(request.request_line, "GET /2023/day/8/input HTTP/1.1");
+        assert!(request
+            .headers
+            .contains(&"Cookie: session=secret".to_string()));
+        assert_eq!(
+            fs::read_to_string(&destination).unwrap(),
+            "RL\n\nAAA = (ZZZ, ZZZ)\n"
+        );
+        assert!(dir.join(".aoc").join("last_request").is_file());
+
+        // the stub only answers once, a second request would fail
+        assert_eq!(
+            client.fetch_input(8, &destination),
+            Ok(Fetched::Cached(destination.clone()))
+        );
+    }
+
+    #[test]
+    fn error_status() {
+        let (base_url, _requests) = stub::serve(vec![(
+            404,
+            "Please don't repeatedly request this endpoint before it unlocks!",
+        )]);
+        let dir = TempDir::new("fetch_404");
+        let client = AocClient {
+            base_url,
+            session: "secret".to_string(),
+            year: 2023,
+            throttle: Duration::ZERO,
+            state_dir: dir.join(".aoc"),
+        };
+        let error = client.fetch_input(25, &dir.join("day_25")).unwrap_err();
+        assert!(error.contains("returned 404"), "{error}");
+        assert!(!dir.join("day_25").exists());
+    }
+}
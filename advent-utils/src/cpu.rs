@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+/// CPU time used by the current thread so far, `None` where it can't be read
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+))]
+fn thread_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: the pointer is to a valid timespec that outlives the call
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) };
+    (result == 0).then(|| Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+fn thread_time() -> Option<Duration> {
+    None
+}
+
+/// Runs `f`, measuring the CPU time it uses on this thread. Threads it spawns aren't counted
+pub(crate) fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<Duration>) {
+    let start = thread_time();
+    let result = f();
+    let time = thread_time()
+        .zip(start)
+        .map(|(end, start)| end.saturating_sub(start));
+    (result, time)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::measure;
+
+    #[test]
+    fn sleeping_is_not_cpu_time() {
+        let (_, busy) = measure(|| {
+            let start = Instant::now();
+            while start.elapsed() < Duration::from_millis(20) {}
+        });
+        let (_, sleeping) = measure(|| thread::sleep(Duration::from_millis(20)));
+        assert!(busy.unwrap() >= Duration::from_millis(10));
+        assert!(sleeping.unwrap() < Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,432 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    answers::{escape, escape_field, unescape},
+    variants::DEFAULT_VARIANT,
+    PuzzleOutcome, TestRunner,
+};
+
+/// A recorded run from the history file
+#[derive(Debug, Clone, PartialEq)]
+struct Run {
+    /// Seconds since the unix epoch
+    timestamp: u64,
+    year: u16,
+    day: u8,
+    part: u8,
+    variant: String,
+    duration: Duration,
+    input_hash: u64,
+    /// Whether the run was from a build with debug assertions, `None` for runs recorded before
+    /// the build profile was
+    debug: Option<bool>,
+    /// Short git revision of the code, `-` when it couldn't be found
+    revision: String,
+    answer: String,
+}
+
+impl Run {
+    fn puzzle(&self) -> (u16, u8, u8, &str) {
+        (self.year, self.day, self.part, &self.variant)
+    }
+}
+
+impl FromStr for Run {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(8, ' ');
+        let mut next = |name| {
+            fields
+                .next()
+                .ok_or_else(|| format!("missing {name} in {s}"))
+        };
+        let timestamp = next("timestamp")?;
+        let year = next("year")?;
+        let day = next("day")?;
+        let part = next("part")?;
+        let variant = next("variant")?;
+        let duration = next("duration")?;
+        let input_hash = next("input hash")?;
+        let rest = next("revision")?;
+        // older lines go straight from the input hash to the revision, which is never a profile
+        let (debug, rest) = match rest.split_once(' ') {
+            Some(("debug", rest)) => (Some(true), rest),
+            Some(("release", rest)) => (Some(false), rest),
+            _ => (None, rest),
+        };
+        let (revision, answer) = rest
+            .split_once(' ')
+            .ok_or_else(|| format!("missing answer in {s}"))?;
+        Ok(Self {
+            timestamp: timestamp
+                .parse()
+                .map_err(|e| format!("bad timestamp {timestamp}: {e}"))?,
+            year: year.parse().map_err(|e| format!("bad year {year}: {e}"))?,
+            day: day.parse().map_err(|e| format!("bad day {day}: {e}"))?,
+            part: part.parse().map_err(|e| format!("bad part {part}: {e}"))?,
+            variant: unescape(variant),
+            duration: duration
+                .parse()
+                .map(Duration::from_nanos)
+                .map_err(|e| format!("bad duration {duration}: {e}"))?,
+            input_hash: u64::from_str_radix(input_hash, 16)
+                .map_err(|e| format!("bad input hash {input_hash}: {e}"))?,
+            debug,
+            revision: revision.to_string(),
+            answer: unescape(answer),
+        })
+    }
+}
+
+/// Every recorded run, one
+/// `timestamp year day part variant duration_nanos input_hash profile revision answer` line
+/// each with the answer escaped like in the answers file and the variant kept to a single field.
+/// `profile` is `debug` or `release`
+#[derive(Debug, Default)]
+struct History {
+    runs: Vec<Run>,
+}
+
+impl History {
+    fn load(path: &Path) -> io::Result<Self> {
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        let runs = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self { runs })
+    }
+
+    /// Runs of each puzzle in the year, oldest first
+    fn by_puzzle(&self, year: u16) -> BTreeMap<(u16, u8, u8, &str), Vec<&Run>> {
+        let mut puzzles = BTreeMap::<_, Vec<_>>::new();
+        for run in self.runs.iter().filter(|run| run.year == year) {
+            puzzles.entry(run.puzzle()).or_default().push(run);
+        }
+        puzzles
+    }
+}
+
+/// How long the latest recorded run of each puzzle's default solution took
+pub(crate) fn latest_durations(path: &Path, year: u16) -> io::Result<BTreeMap<(u8, u8), Duration>> {
+    Ok(History::load(path)?
+        .runs
+        .into_iter()
+        .filter(|run| run.year == year && run.variant == DEFAULT_VARIANT)
+        .map(|run| ((run.day, run.part), run.duration))
+        .collect())
+}
+
+/// Appends the successful outcomes to the history file
+pub(crate) fn record(path: &Path, outcomes: &[PuzzleOutcome]) -> io::Result<()> {
+    let runs = outcomes
+        .iter()
+        .filter_map(|outcome| Some((outcome, outcome.answer.as_ref().ok()?, outcome.input_hash?)))
+        .collect::<Vec<_>>();
+    if runs.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let revision = git_revision().unwrap_or_else(|| "-".to_string());
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for (outcome, answer, input_hash) in runs {
+        writeln!(
+            file,
+            "{timestamp} {} {} {} {} {} {input_hash:x} {profile} {revision} {}",
+            outcome.year,
+            outcome.day,
+            outcome.part,
+            escape_field(outcome.variant_name()),
+            outcome.elapsed.as_nanos(),
+            escape(answer),
+        )?;
+    }
+    Ok(())
+}
+
+fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Hashes puzzle input with FNV-1a, which unlike the std hashers is stable between builds
+pub(crate) fn hash_input(lines: &[String]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in lines.iter().flat_map(|line| line.bytes().chain([b'\n'])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Something that changed in a puzzle's latest run compared to the runs before it
+#[derive(Debug, PartialEq)]
+enum Finding {
+    Slower { latest: Duration, median: Duration },
+    AnswerChanged { previous: String, latest: String },
+}
+
+/// Compares the latest run against earlier runs on the same input, a run is slower when it took
+/// more than `threshold` times the median of the earlier runs from the same build profile
+fn compare(runs: &[&Run], threshold: f64) -> Vec<Finding> {
+    let Some((latest, earlier)) = runs.split_last() else {
+        return vec![];
+    };
+    let earlier = earlier
+        .iter()
+        .filter(|run| run.input_hash == latest.input_hash)
+        .collect::<Vec<_>>();
+    let mut findings = vec![];
+    if let Some(previous) = earlier.last() {
+        if previous.answer != latest.answer {
+            findings.push(Finding::AnswerChanged {
+                previous: previous.answer.clone(),
+                latest: latest.answer.clone(),
+            });
+        }
+    }
+    // debug builds are much slower, so only timings from the same profile are comparable
+    let mut timings = earlier
+        .into_iter()
+        .filter(|run| run.debug == latest.debug)
+        .collect::<Vec<_>>();
+    timings.sort_by_key(|run| run.duration);
+    if let Some(median) = timings.get(timings.len() / 2).map(|run| run.duration) {
+        if latest.duration.as_secs_f64() > median.as_secs_f64() * threshold {
+            findings.push(Finding::Slower {
+                latest: latest.duration,
+                median,
+            });
+        }
+    }
+    findings
+}
+
+/// Describes how long ago a timestamp was
+fn ago(timestamp: u64, now: u64) -> String {
+    let seconds = now.saturating_sub(timestamp);
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
+
+fn puzzle_name(day: u8, part: u8, variant: &str) -> String {
+    if variant == DEFAULT_VARIANT {
+        format!("Day {day} Part {part}")
+    } else {
+        format!("Day {day} Part {part} ({variant})")
+    }
+}
+
+impl TestRunner {
+    /// Prints the most recent runs of each puzzle in the year
+    pub fn history(path: &Path, year: u16, day: Option<u8>, limit: usize) -> io::Result<bool> {
+        let history = History::load(path)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let puzzles = history.by_puzzle(year);
+        if puzzles.is_empty() {
+            println!("No runs recorded for {year} yet");
+        }
+        for ((_, puzzle_day, part, variant), runs) in puzzles {
+            if day.is_some_and(|day| day != puzzle_day) {
+                continue;
+            }
+            println!("{}", puzzle_name(puzzle_day, part, variant));
+            for run in runs.iter().skip(runs.len().saturating_sub(limit)) {
+                println!(
+                    "  {:>9} {:>8} {:>10} {:016x} {}",
+                    ago(run.timestamp, now),
+                    run.revision,
+                    format!("{:.2?}", run.duration),
+                    run.input_hash,
+                    run.answer
+                );
+            }
+        }
+        Ok(true)
+    }
+
+    /// Flags puzzles whose latest run got slower or changed answer, returns false if any did
+    pub fn compare_history(path: &Path, year: u16, threshold: f64) -> io::Result<bool> {
+        let history = History::load(path)?;
+        let mut passed = true;
+        for ((_, day, part, variant), runs) in history.by_puzzle(year) {
+            let name = puzzle_name(day, part, variant);
+            let findings = compare(&runs, threshold);
+            if findings.is_empty() {
+                println!("{name}: OK");
+            }
+            for finding in findings {
+                passed = false;
+                match finding {
+                    Finding::Slower { latest, median } => println!(
+                        "{name}: SLOWER {latest:.2?}, median of earlier runs was {median:.2?}"
+                    ),
+                    Finding::AnswerChanged { previous, latest } => {
+                        println!("{name}: CHANGED answer {latest}, was {previous}")
+                    }
+                }
+            }
+        }
+        Ok(passed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{PuzzleOutcome, TempDir, DEFAULT_YEAR};
+
+    use super::{ago, compare, hash_input, record, Finding, History, Run};
+
+    fn run(duration: u64, input_hash: u64, answer: &str) -> Run {
+        Run {
+            timestamp: 0,
+            year: 2023,
+            day: 1,
+            part: 1,
+            variant: "default".to_string(),
+            duration: Duration::from_millis(duration),
+            input_hash,
+            debug: Some(false),
+            revision: "-".to_string(),
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn findings() {
+        let runs = [
+            run(10, 1, "142"),
+            run(12, 1, "142"),
+            run(500, 2, "281"),
+            run(11, 1, "142"),
+        ];
+        let runs = runs.iter().collect::<Vec<_>>();
+        assert_eq!(compare(&runs, 1.5), vec![]);
+
+        let slower = run(20, 1, "142");
+        let changed = run(11, 1, "143");
+        assert_eq!(
+            compare(&[runs.as_slice(), &[&slower]].concat(), 1.5),
+            vec![Finding::Slower {
+                latest: Duration::from_millis(20),
+                median: Duration::from_millis(11),
+            }]
+        );
+        assert_eq!(
+            compare(&[runs.as_slice(), &[&changed]].concat(), 1.5),
+            vec![Finding::AnswerChanged {
+                previous: "142".to_string(),
+                latest: "143".to_string(),
+            }]
+        );
+        // debug builds are only timed against each other, their answers still count
+        let debug = Run {
+            debug: Some(true),
+            ..run(900, 1, "143")
+        };
+        assert_eq!(
+            compare(&[runs.as_slice(), &[&debug]].concat(), 1.5),
+            vec![Finding::AnswerChanged {
+                previous: "142".to_string(),
+                latest: "143".to_string(),
+            }]
+        );
+        // nothing to compare a new input against
+        assert_eq!(compare(&[&run(900, 3, "1")], 1.5), vec![]);
+    }
+
+    #[test]
+    fn records() {
+        let dir = TempDir::new("history");
+        let path = dir.join("history");
+        let input = vec!["two1nine".to_string(), "eightwothree".to_string()];
+        let outcome = PuzzleOutcome {
+            elapsed: Duration::from_micros(1500),
+            input_hash: Some(hash_input(&input)),
+            ..PuzzleOutcome::test(1, 2, Ok("two words"))
+        };
+        record(&path, &[outcome]).unwrap();
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.runs.len(), 1);
+        let run = &history.runs[0];
+        assert_eq!(run.puzzle(), (DEFAULT_YEAR, 1, 2, "default"));
+        assert_eq!(run.duration, Duration::from_micros(1500));
+        assert_eq!(run.input_hash, hash_input(&input));
+        assert_eq!(run.answer, "two words");
+        assert_eq!(run.debug, Some(cfg!(debug_assertions)));
+
+        let letters = PuzzleOutcome {
+            input_hash: Some(hash_input(&input)),
+            ..PuzzleOutcome::test(10, 2, Ok("#..#\n####"))
+        };
+        record(&path, &[letters]).unwrap();
+        let history = History::load(&path).unwrap();
+        assert_eq!(history.runs.len(), 2);
+        assert_eq!(history.runs[1].answer, "#..#\n####");
+
+        // the macro rejects these, but a space in the variant mustn't break the whole file
+        let spaced = PuzzleOutcome {
+            variant: Some("brute force"),
+            input_hash: Some(hash_input(&input)),
+            ..PuzzleOutcome::test(5, 2, Ok("46"))
+        };
+        record(&path, &[spaced]).unwrap();
+        let history = History::load(&path).unwrap();
+        assert_eq!(
+            history.runs[2].puzzle(),
+            (DEFAULT_YEAR, 5, 2, "brute force")
+        );
+        assert_eq!(history.runs[2].answer, "46");
+
+        // recorded before the build profile was
+        let old = "1700000000 2023 1 2 default 1500000 ff abc1234 two words"
+            .parse::<Run>()
+            .unwrap();
+        assert_eq!(old.debug, None);
+        assert_eq!(old.revision, "abc1234");
+        assert_eq!(old.answer, "two words");
+        assert_ne!(hash_input(&input), hash_input(&input[..1]));
+        assert_eq!(ago(100, 7300), "2h ago");
+    }
+}
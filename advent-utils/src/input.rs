@@ -0,0 +1,234 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+};
+
+use crate::{read_lines, RunOptions, TestRunner};
+
+/// Profile used when a day's input is a directory of profiles and none was picked
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Where a puzzle reads its input from
+///
+/// A day's input is either a single file, or a directory holding one `<profile>.txt` file per
+/// person's input
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum InputSource {
+    /// The puzzle's own input file, or the default profile
+    #[default]
+    Default,
+    /// A named profile from the day's input directory
+    Profile(String),
+    File(PathBuf),
+    Stdin,
+    /// Input given directly on the command line
+    Inline(String),
+}
+
+impl InputSource {
+    /// A path of `-` reads from stdin
+    pub fn from_path(path: PathBuf) -> Self {
+        if path.as_os_str() == "-" {
+            Self::Stdin
+        } else {
+            Self::File(path)
+        }
+    }
+
+    /// The file this source reads, `None` for stdin and inline input
+    pub(crate) fn path(&self, default_file: &str) -> Option<PathBuf> {
+        match self {
+            InputSource::Default if Path::new(default_file).is_dir() => {
+                Some(profile_path(default_file, DEFAULT_PROFILE))
+            }
+            InputSource::Default => Some(PathBuf::from(default_file)),
+            InputSource::Profile(profile) => Some(profile_path(default_file, profile)),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin | InputSource::Inline(_) => None,
+        }
+    }
+
+    pub(crate) fn read_lines(&self, default_file: &str) -> Result<Vec<String>, String> {
+        let lines = match self {
+            InputSource::Default if Path::new(default_file).is_dir() => {
+                read_lines(profile_path(default_file, DEFAULT_PROFILE))
+            }
+            InputSource::Default => read_lines(default_file),
+            InputSource::Profile(profile) => read_lines(profile_path(default_file, profile)),
+            InputSource::File(path) => read_lines(path),
+            InputSource::Stdin => io::stdin().lock().lines().collect(),
+            InputSource::Inline(input) => Ok(input.lines().map(str::to_string).collect()),
+        };
+        lines.map_err(|e| match self {
+            InputSource::Default => format!("unable to open {default_file}: {e}"),
+            InputSource::Profile(profile) => format!(
+                "unable to open {}: {e}",
+                profile_path(default_file, profile).display()
+            ),
+            source => format!("unable to read {source}: {e}"),
+        })
+    }
+}
+
+fn profile_path(default_file: &str, profile: &str) -> PathBuf {
+    Path::new(default_file).join(format!("{profile}.txt"))
+}
+
+/// Names of every profile in a day's input directory, sorted
+pub(crate) fn profiles(default_file: &str) -> io::Result<Vec<String>> {
+    let mut profiles = vec![];
+    for entry in fs::read_dir(default_file)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "txt") {
+            if let Some(profile) = path.file_stem().and_then(|stem| stem.to_str()) {
+                profiles.push(profile.to_string());
+            }
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Default => write!(f, "default input"),
+            InputSource::Profile(profile) => write!(f, "profile {profile}"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Inline(_) => write!(f, "example input"),
+        }
+    }
+}
+
+impl TestRunner {
+    /// Reads the puzzle's input, the default input comes from the binary when it was embedded
+    pub(crate) fn read_input(&self, input: &InputSource) -> Result<Vec<String>, String> {
+        match (input, self.embedded) {
+            (InputSource::Default, Some(embedded)) => {
+                Ok(embedded.lines().map(str::to_string).collect())
+            }
+            _ => input.read_lines(self.file),
+        }
+    }
+
+    /// Size of the puzzle's default input, `None` if it doesn't exist
+    pub(crate) fn input_size(&self) -> Option<u64> {
+        if let Some(embedded) = self.embedded {
+            return Some(embedded.len() as u64);
+        }
+        let path = InputSource::Default.path(self.file)?;
+        fs::metadata(path).ok().map(|metadata| metadata.len())
+    }
+
+    /// Runs a single puzzle against every profile in its input directory and prints a table of
+    /// the answers, returns false if any profile failed
+    pub fn run_profiles(day: u8, part: u8, options: &RunOptions) -> bool {
+        let test = match Self::find(options.year, day, part, options.variant.as_deref()) {
+            Ok(test) => test,
+            Err(e) => {
+                eprintln!("{e}");
+                return false;
+            }
+        };
+        let profiles = match profiles(test.file) {
+            Ok(profiles) if !profiles.is_empty() => profiles,
+            Ok(_) => {
+                println!("No profiles found in {}", test.file);
+                return false;
+            }
+            Err(e) => {
+                println!("Unable to list profiles in {}: {e}", test.file);
+                return false;
+            }
+        };
+        let width = profiles.iter().map(String::len).max().unwrap_or_default();
+        println!("Day: {day} Part: {part}");
+        println!("{:<width$} {:>12} Answer", "Profile", "Elapsed");
+        let mut passed = true;
+        for profile in profiles {
+            let outcome = test.run_with_timeout(
+                &InputSource::Profile(profile.clone()),
+                options.timeouts.for_puzzle(day, part),
+            );
+            let elapsed = format!("{:.2?}", outcome.elapsed);
+            match outcome.answer {
+                Ok(answer) => println!("{profile:<width$} {elapsed:>12} {answer}"),
+                Err(failure) => {
+                    passed = false;
+                    println!("{profile:<width$} {elapsed:>12} FAILED: {failure}");
+                }
+            }
+        }
+        passed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use crate::{TempDir, TestRunner};
+
+    use super::{profiles, InputSource};
+
+    #[test]
+    fn sources() {
+        assert_eq!(
+            InputSource::from_path(PathBuf::from("-")),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::Inline("RL\n\nAAA = (BBB, CCC)".to_string()).read_lines("unused"),
+            Ok(vec![
+                "RL".to_string(),
+                String::new(),
+                "AAA = (BBB, CCC)".to_string()
+            ])
+        );
+        assert!(InputSource::File(PathBuf::from("./no/such/file"))
+            .read_lines("unused")
+            .unwrap_err()
+            .starts_with("unable to read ./no/such/file"));
+    }
+
+    #[test]
+    fn profile_directory() {
+        let dir = TempDir::new("profiles");
+        fs::write(dir.join("default.txt"), "1\n2").unwrap();
+        fs::write(dir.join("bob.txt"), "3").unwrap();
+        fs::write(dir.join("notes.md"), "not a profile").unwrap();
+        let day_dir = dir.to_str().unwrap();
+        assert_eq!(profiles(day_dir).unwrap(), vec!["bob", "default"]);
+        assert_eq!(
+            InputSource::Default.read_lines(day_dir),
+            Ok(vec!["1".to_string(), "2".to_string()])
+        );
+        assert_eq!(
+            InputSource::Profile("bob".to_string()).read_lines(day_dir),
+            Ok(vec!["3".to_string()])
+        );
+        assert!(InputSource::Profile("alice".to_string())
+            .read_lines(day_dir)
+            .is_err());
+    }
+
+    #[test]
+    fn embedded() {
+        let test = TestRunner {
+            embedded: Some("1\n2\n"),
+            ..TestRunner::test(1, 1, None, "day1::part_1")
+        };
+        assert_eq!(
+            test.read_input(&InputSource::Default),
+            Ok(vec!["1".to_string(), "2".to_string()])
+        );
+        assert_eq!(
+            test.read_input(&InputSource::Inline("3".to_string())),
+            Ok(vec!["3".to_string()])
+        );
+        assert_eq!(test.input_size(), Some(4));
+    }
+}
@@ -1,32 +1,291 @@
+mod alloc;
+mod answers;
+mod bench;
+mod client;
+mod cpu;
+mod history;
+mod input;
+mod list;
+mod parser;
+mod pool;
+mod report;
+mod scaffold;
+mod spans;
+mod submit;
+mod timeout;
+mod tui;
+mod variants;
+mod watch;
+
+pub use alloc::{AllocStats, CountingAllocator};
+pub use answers::Answers;
+pub use bench::BenchOptions;
+pub use client::{AocClient, Fetched, DEFAULT_BASE_URL, SESSION_ENV, STATE_DIR};
+pub use input::{InputSource, DEFAULT_PROFILE};
+pub use report::ReportFormat;
+pub use scaffold::{scaffold_day, Scaffolded};
+pub use spans::{span, Span, SpanGuard};
+pub use submit::Verdict;
+pub use timeout::{parse_seconds, PuzzleTimeout, Timeouts};
+
 use core::fmt::Debug;
 use std::{
+    any::Any,
+    cell::Cell,
+    error::Error,
+    fmt::{self, Display},
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
     str::FromStr,
-    time::Instant,
+    sync::Once,
+    time::{Duration, Instant},
 };
 
+/// Error type a puzzle can fail with, any `Into<PuzzleError>` error can be returned from a solution
+pub type PuzzleError = Box<dyn Error + Send + Sync>;
+pub type PuzzleResult = Result<String, PuzzleError>;
+
+/// Year of puzzles registered without a `year`
+pub const DEFAULT_YEAR: u16 = 2023;
+
 pub struct TestRunner {
-    run_test: &'static (dyn Fn() + Send + Sync + 'static),
+    solve: &'static (dyn Fn(Vec<String>) -> Result<String, String> + Send + Sync + 'static),
+    file: &'static str,
+    /// Input baked into the binary by the `embed` feature, used instead of `file`
+    embedded: Option<&'static str>,
+    year: u16,
     day: u8,
     part: u8,
+    variant: Option<&'static str>,
+    /// Path of the function solving the puzzle
+    source: &'static str,
+    /// Parse step shared with the day's other part, for solutions taking `#[advent_parser]` output
+    shared: Option<parser::SharedParse>,
+}
+
+/// Settings for how puzzles are run
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Only puzzles from this year are run
+    pub year: u16,
+    /// Number of puzzles to run at the same time
+    pub jobs: usize,
+    pub timeouts: Timeouts,
+    pub format: ReportFormat,
+    /// Where each puzzle reads its input from
+    pub input: InputSource,
+    /// Which solution to use when running a single puzzle, defaults to the unnamed one
+    pub variant: Option<String>,
+    /// Successful runs are appended to this file, runs on inline input or stdin aren't kept
+    pub history: Option<PathBuf>,
+}
+
+impl RunOptions {
+    fn record_history(&self, outcomes: &[PuzzleOutcome]) {
+        let Some(path) = &self.history else {
+            return;
+        };
+        if matches!(self.input, InputSource::Inline(_) | InputSource::Stdin) {
+            return;
+        }
+        if let Err(e) = history::record(path, outcomes) {
+            eprintln!("unable to record history in {}: {e}", path.display());
+        }
+    }
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            year: DEFAULT_YEAR,
+            jobs: 0,
+            timeouts: Timeouts::default(),
+            format: ReportFormat::default(),
+            input: InputSource::default(),
+            variant: None,
+            history: None,
+        }
+    }
 }
 
 impl TestRunner {
-    pub fn run_test(day: u8, part: u8) {
-        let test = inventory::iter::<TestRunner>()
-            .find(|runner| runner.day == day && runner.part == part)
-            .unwrap();
-        (test.run_test)();
+    /// Runs a single puzzle, returns false if it failed
+    pub fn run_test(day: u8, part: u8, options: &RunOptions) -> bool {
+        let test = match Self::find(options.year, day, part, options.variant.as_deref()) {
+            Ok(test) => test,
+            Err(e) => {
+                eprintln!("{e}");
+                return false;
+            }
+        };
+        let outcome = test.run_with_timeout(&options.input, options.timeouts.for_puzzle(day, part));
+        options.format.outcome(&outcome);
+        let passed = outcome.answer.is_ok();
+        let outcomes = [outcome];
+        options.format.finish(&outcomes, None);
+        options.record_history(&outcomes);
+        passed
     }
 
-    pub fn run_all() {
+    /// Runs every registered puzzle, continuing past failures, returns false if any failed
+    ///
+    /// Results are printed in day/part order regardless of which puzzle finishes first. Parts
+    /// sharing a parse step have their input parsed once for both
+    pub fn run_all(options: &RunOptions) -> bool {
+        let start = Instant::now();
+        let mut outcomes = vec![];
+        let tests = Self::sorted(options.year);
+        pool::run_in_order(
+            &parser::groups(&tests),
+            options.jobs,
+            |group| Self::run_group(group, &options.input, &options.timeouts),
+            |group| {
+                for outcome in group {
+                    options.format.outcome(&outcome);
+                    outcomes.push(outcome);
+                }
+            },
+        );
+        options.format.finish(&outcomes, Some(start.elapsed()));
+        options.record_history(&outcomes);
+        outcomes.iter().all(|outcome| outcome.answer.is_ok())
+    }
+
+    /// Finds a puzzle's solution, without a variant the unnamed solution is preferred
+    ///
+    /// Fails with a description of the problem when the puzzle or variant isn't registered, or
+    /// when more than one function is registered for it
+    fn find(
+        year: u16,
+        day: u8,
+        part: u8,
+        variant: Option<&str>,
+    ) -> Result<&'static TestRunner, String> {
+        let puzzle = Self::variants(year)
+            .into_iter()
+            .filter(|runner| runner.day == day && runner.part == part)
+            .collect::<Vec<_>>();
+        let Some(first) = puzzle.first() else {
+            return Err(format!("Day {day} Part {part} isn't registered for {year}"));
+        };
+        let variant = match variant {
+            Some(variant) => Some(variant),
+            None => first.variant,
+        };
+        let matching = puzzle
+            .iter()
+            .filter(|runner| runner.variant == variant)
+            .collect::<Vec<_>>();
+        match matching.as_slice() {
+            [runner] => Ok(runner),
+            [] => Err(format!(
+                "Day {day} Part {part} has no {} variant, registered variants are {}",
+                variant.unwrap_or(variants::DEFAULT_VARIANT),
+                puzzle
+                    .iter()
+                    .map(|runner| runner.variant.unwrap_or(variants::DEFAULT_VARIANT))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            duplicates => Err(format!(
+                "Day {day} Part {part} is registered more than once, by {}",
+                duplicates
+                    .iter()
+                    .map(|runner| runner.source)
+                    .collect::<Vec<_>>()
+                    .join(" and ")
+            )),
+        }
+    }
+
+    /// Every day of the year with at least one registered puzzle
+    pub fn days(year: u16) -> Vec<u8> {
+        let mut days = Self::sorted(year)
+            .into_iter()
+            .map(|test| test.day)
+            .collect::<Vec<_>>();
+        days.dedup();
+        days
+    }
+
+    /// One solution per puzzle registered for the year, ordered by day then part
+    ///
+    /// Puzzles with several variants use the unnamed one, or the first named one without it
+    fn sorted(year: u16) -> Vec<&'static TestRunner> {
+        let mut tests = Self::variants(year);
+        tests.dedup_by_key(|test| (test.day, test.part));
+        tests
+    }
+
+    /// Every solution registered for the year, ordered by day, part then variant with the
+    /// unnamed variant first
+    fn variants(year: u16) -> Vec<&'static TestRunner> {
         let mut tests = inventory::iter::<TestRunner>
             .into_iter()
+            .filter(|test| test.year == year)
             .collect::<Vec<_>>();
-        tests.sort_by_key(|test| (test.day, test.part));
-        for test in tests {
-            (test.run_test)();
+        tests.sort_by_key(|test| (test.day, test.part, test.variant));
+        tests
+    }
+
+    /// Reads the puzzle input and solves it
+    ///
+    /// Solutions with a shared parse step have it timed separately from solving
+    fn run(&self, input: &InputSource) -> PuzzleOutcome {
+        let start = Instant::now();
+        let lines = match self.read_input(input) {
+            Ok(lines) => lines,
+            Err(e) => return self.failed(Failure::Error(e), start.elapsed()),
+        };
+        // hashing is bookkeeping for the history, so it's left out of the elapsed time
+        let hash_start = Instant::now();
+        let input_hash = history::hash_input(&lines);
+        let hashing = hash_start.elapsed();
+        let (((answer, allocations), spans), cpu) = cpu::measure(|| {
+            spans::collect(|| {
+                alloc::measure(|| match self.shared {
+                    Some(shared) => {
+                        let parse_start = Instant::now();
+                        let parsed = shared.parse(lines);
+                        let parse = parse_start.elapsed();
+                        (parsed.and_then(|parsed| shared.solve(&parsed)), Some(parse))
+                    }
+                    None => ((self.solve)(lines), None),
+                })
+            })
+        });
+        let (answer, parse) = answer;
+        PuzzleOutcome {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            variant: self.variant,
+            answer: answer.map_err(Failure::Error),
+            elapsed: start.elapsed() - hashing - parse.unwrap_or_default(),
+            parse,
+            cpu,
+            allocations,
+            input_hash: Some(input_hash),
+            spans,
+        }
+    }
+
+    /// Outcome of a run that didn't get an answer
+    fn failed(&self, failure: Failure, elapsed: Duration) -> PuzzleOutcome {
+        PuzzleOutcome {
+            year: self.year,
+            day: self.day,
+            part: self.part,
+            variant: self.variant,
+            answer: Err(failure),
+            elapsed,
+            parse: None,
+            cpu: None,
+            allocations: None,
+            input_hash: None,
+            spans: vec![],
         }
     }
 
@@ -35,32 +294,277 @@ impl TestRunner {
         D: DayPart + 'static,
     {
         Self {
-            run_test: &run_test::<D>,
+            solve: &solve::<D>,
+            file: D::FILE,
+            embedded: D::INPUT,
+            year: D::YEAR,
             day: D::DAY,
             part: D::PART,
+            variant: D::VARIANT,
+            source: D::SOURCE,
+            shared: None,
+        }
+    }
+
+    /// Registers a part solved from its day's `#[advent_parser]` output, so running both parts
+    /// only parses the input once
+    pub const fn parsed<D>() -> Self
+    where
+        D: ParsedDayPart + 'static,
+    {
+        Self {
+            shared: Some(parser::SharedParse::new::<D>()),
+            ..Self::new::<D>()
+        }
+    }
+}
+
+#[cfg(test)]
+impl TestRunner {
+    /// A 2023 puzzle solved with an empty answer, reading a default input that doesn't exist.
+    /// Tests set any other fields they need on top of it
+    pub(crate) fn test(
+        day: u8,
+        part: u8,
+        variant: Option<&'static str>,
+        source: &'static str,
+    ) -> Self {
+        Self {
+            solve: &|_| Ok(String::new()),
+            file: "./no/such/file",
+            embedded: None,
+            year: DEFAULT_YEAR,
+            day,
+            part,
+            variant,
+            source,
+            shared: None,
         }
     }
 }
 
+/// A directory for a test's files, removed again when dropped so a failing test doesn't leave
+/// it behind
+#[cfg(test)]
+pub(crate) struct TempDir(PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    /// Creates an empty `advent_<name>_<pid>` directory in the system's temp dir, `name` keeps
+    /// tests running at the same time apart
+    pub(crate) fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("advent_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+#[cfg(test)]
+impl std::ops::Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
 inventory::collect!(TestRunner);
 
-pub fn run_test<D>()
+/// The result of running a single day/part
+pub struct PuzzleOutcome {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// Which solution produced the answer, `None` for the unnamed one
+    pub variant: Option<&'static str>,
+    /// The answer, or why the puzzle didn't produce one
+    pub answer: Result<String, Failure>,
+    /// Time spent solving, not including the shared parse step
+    pub elapsed: Duration,
+    /// Time spent in the day's shared parse step, `None` for solutions that parse their own input
+    pub parse: Option<Duration>,
+    /// CPU time of the thread solving the puzzle, including the shared parse step for the first
+    /// part of a day. `None` on platforms where it can't be measured
+    pub cpu: Option<Duration>,
+    /// What solving allocated, when the `CountingAllocator` is installed
+    pub allocations: Option<AllocStats>,
+    /// Hash of the input the puzzle was solved with, `None` if it couldn't be read
+    pub input_hash: Option<u64>,
+    /// Phases the solution marked with `span!`
+    pub spans: Vec<Span>,
+}
+
+/// Why a puzzle didn't produce an answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Failure {
+    /// The solution returned an error or panicked
+    Error(String),
+    /// The solution was still running when its timeout passed
+    Timeout(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Error(error) => write!(f, "{error}"),
+            Failure::Timeout(timeout) => write!(f, "TIMEOUT after {timeout:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+impl PuzzleOutcome {
+    /// Outcome of the unnamed solution to a 2023 puzzle that took no time, with an error
+    /// standing in for a failed run. Tests set any other fields they need on top of it
+    pub(crate) fn test(day: u8, part: u8, answer: Result<&str, &str>) -> Self {
+        Self {
+            year: DEFAULT_YEAR,
+            day,
+            part,
+            variant: None,
+            answer: answer
+                .map(ToString::to_string)
+                .map_err(|e| Failure::Error(e.to_string())),
+            elapsed: Duration::ZERO,
+            parse: None,
+            cpu: None,
+            allocations: None,
+            input_hash: None,
+            spans: vec![],
+        }
+    }
+}
+
+impl PuzzleOutcome {
+    pub(crate) fn print(&self) {
+        let Self {
+            day,
+            part,
+            variant,
+            answer,
+            elapsed,
+            parse,
+            allocations,
+            spans,
+            ..
+        } = self;
+        match variant {
+            Some(variant) => println!("Day: {day} Part: {part} Variant: {variant}"),
+            None => println!("Day: {day} Part: {part}"),
+        }
+        match answer {
+            Ok(answer) => println!("{answer}"),
+            Err(error @ Failure::Error(_)) => println!("FAILED: {error}"),
+            Err(timeout @ Failure::Timeout(_)) => println!("{timeout}"),
+        }
+        if let Some(parse) = parse {
+            println!("Parse: {parse:?}");
+        }
+        match allocations {
+            Some(allocations) => println!("Elapsed: {elapsed:?} ({allocations})"),
+            None => println!("Elapsed: {elapsed:?}"),
+        }
+        spans::print_spans(spans, *elapsed, 0);
+    }
+}
+
+pub(crate) fn print_summary(outcomes: &[PuzzleOutcome]) {
+    let failed = outcomes
+        .iter()
+        .filter_map(|outcome| Some((outcome, outcome.answer.as_ref().err()?)))
+        .collect::<Vec<_>>();
+    let timed_out = failed
+        .iter()
+        .filter(|(_, failure)| matches!(failure, Failure::Timeout(_)))
+        .count();
+    println!(
+        "{} passed, {} failed, {timed_out} timed out",
+        outcomes.len() - failed.len(),
+        failed.len() - timed_out,
+    );
+    for (outcome, error) in failed {
+        println!("  Day {} Part {}: {error}", outcome.day, outcome.part);
+    }
+}
+
+thread_local! {
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs the solution, turning an error or a panic inside of it into a failure message
+fn catch_panics<T>(run: impl FnOnce() -> Result<T, PuzzleError>) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    // the panic message ends up in the outcome, so keep the default hook from printing it.
+    // the hook is shared by every thread so it's only installed once
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANICS.get() {
+                hook(info)
+            }
+        }));
+    });
+    CATCHING_PANICS.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(run));
+    CATCHING_PANICS.set(false);
+    match result {
+        Ok(answer) => answer.map_err(|e| e.to_string()),
+        Err(payload) => Err(format!("panicked: {}", panic_message(&*payload))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+pub fn solve<D>(lines: Vec<String>) -> Result<String, String>
 where
     D: DayPart,
 {
-    let start = Instant::now();
-    println!("Day: {} Part: {}", D::DAY, D::PART);
-    let lines = read_file(D::FILE);
-    let result = D::run(lines);
-    println!("{}", result);
-    println!("Elapsed: {:?}", start.elapsed());
+    catch_panics(|| D::run(lines.into_iter()))
 }
+
 pub trait DayPart {
     const FILE: &'static str;
+    /// The contents of `FILE` when inputs are embedded in the binary
+    const INPUT: Option<&'static str> = None;
+    const YEAR: u16;
     const DAY: u8;
     const PART: u8;
+    /// Name of an alternative solution, `None` for the usual one
+    const VARIANT: Option<&'static str> = None;
+    /// Path of the function solving the puzzle, shown when listing puzzles
+    const SOURCE: &'static str = "unknown";
+
+    fn run(lines: impl Iterator<Item = String>) -> PuzzleResult;
+}
 
-    fn run(lines: impl Iterator<Item = String>) -> String;
+/// Parse step shared by both parts of a day, generated by `#[advent_parser]`
+pub trait DayParser {
+    type Output: Send + Sync + 'static;
+
+    fn parse(lines: impl Iterator<Item = String>) -> Result<Self::Output, PuzzleError>;
+}
+
+/// A part solved from its day's parsed input rather than the input lines
+pub trait ParsedDayPart: DayPart {
+    type Parser: DayParser;
+
+    fn solve(parsed: &<Self::Parser as DayParser>::Output) -> PuzzleResult;
 }
 
 pub trait ParseExt {
@@ -96,10 +600,8 @@ where
     }
 }
 
-pub fn read_file(file: &str) -> impl Iterator<Item = String> {
-    let read = File::open(file).unwrap();
-    let reader = BufReader::new(read);
-    reader.lines().map(|l| l.unwrap())
+pub fn read_lines(file: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    BufReader::new(File::open(file)?).lines().collect()
 }
 
 pub struct TupleIter<I>(I)
@@ -177,7 +679,7 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         let Self { iter, last_item } = self;
         let mut current_count = 1;
-        while let Some(item) = iter.next() {
+        for item in iter.by_ref() {
             if let Some(last) = last_item.take() {
                 if last != item {
                     *last_item = Some(item);
@@ -198,14 +700,81 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::IterExt;
+    use crate::{catch_panics, DayPart, IterExt, PuzzleResult, TestRunner};
+
+    struct Year2022Day1Part1;
+
+    impl DayPart for Year2022Day1Part1 {
+        const FILE: &'static str = "./test_data/2022/day_1";
+        const YEAR: u16 = 2022;
+        const DAY: u8 = 1;
+        const PART: u8 = 1;
+
+        fn run(_: impl Iterator<Item = String>) -> PuzzleResult {
+            Ok("2022".to_string())
+        }
+    }
+
+    struct Year2023Day1Part1;
+
+    impl DayPart for Year2023Day1Part1 {
+        const FILE: &'static str = "./test_data/day_1";
+        const YEAR: u16 = 2023;
+        const DAY: u8 = 1;
+        const PART: u8 = 1;
+
+        fn run(_: impl Iterator<Item = String>) -> PuzzleResult {
+            Ok("2023".to_string())
+        }
+    }
+
+    inventory::submit! { TestRunner::new::<Year2022Day1Part1>() }
+    inventory::submit! { TestRunner::new::<Year2023Day1Part1>() }
 
     #[test]
     fn dedup() {
-        let mut counts = "aabbcccdddde".chars().count_distinct().collect::<Vec<_>>();
+        let counts = "aabbcccdddde".chars().count_distinct().collect::<Vec<_>>();
         assert_eq!(
             counts,
             vec![(2, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (1, 'e')]
         );
     }
+
+    #[test]
+    fn failures() {
+        assert_eq!(catch_panics(|| Ok("42".to_string())), Ok("42".to_string()));
+        assert_eq!(
+            catch_panics::<String>(|| Err("bad input".into())),
+            Err("bad input".to_string())
+        );
+        assert_eq!(
+            catch_panics::<String>(|| panic!("unable to find number")),
+            Err("panicked: unable to find number".to_string())
+        );
+    }
+
+    #[test]
+    fn years() {
+        for year in [2022, 2023] {
+            let test = TestRunner::find(year, 1, 1, None).unwrap();
+            assert_eq!((test.solve)(vec![]), Ok(year.to_string()));
+            assert_eq!(TestRunner::days(year), vec![1]);
+        }
+        assert_eq!(
+            TestRunner::find(2021, 1, 1, None).err().as_deref(),
+            Some("Day 1 Part 1 isn't registered for 2021")
+        );
+        assert_eq!(
+            TestRunner::find(2022, 1, 1, Some("fast")).err().as_deref(),
+            Some("Day 1 Part 1 has no fast variant, registered variants are default")
+        );
+        assert_eq!(
+            TestRunner::input_path(2022, 1, None).to_str(),
+            Some("./test_data/2022/day_1")
+        );
+        assert_eq!(
+            TestRunner::input_path(2022, 2, None).to_str(),
+            Some("./test_data/2022/day_2")
+        );
+    }
 }
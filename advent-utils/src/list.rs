@@ -0,0 +1,196 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use crate::{alloc::format_bytes, variants::DEFAULT_VARIANT, InputSource, TestRunner};
+
+/// The last day of the event, which only has a single part
+const LAST_DAY: u8 = 25;
+
+/// Something wrong with how a year's puzzles are registered
+#[derive(Debug, PartialEq)]
+enum Problem {
+    /// A day between the first and last registered ones has nothing registered
+    MissingDay(u8),
+    /// One part of a day is registered but the other isn't, `other` is where the registered
+    /// part is
+    MissingPart {
+        day: u8,
+        part: u8,
+        other: &'static str,
+    },
+    /// Several functions are registered for the same puzzle and variant
+    Duplicate {
+        day: u8,
+        part: u8,
+        variant: &'static str,
+        sources: Vec<&'static str>,
+    },
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::MissingDay(day) => write!(f, "Day {day} has no registered puzzles"),
+            Problem::MissingPart { day, part, other } => write!(
+                f,
+                "Day {day} Part {part} isn't registered, the other part is {other}, is its \
+                 module missing an #[advent_of_code] function?"
+            ),
+            Problem::Duplicate {
+                day,
+                part,
+                variant,
+                sources,
+            } => write!(
+                f,
+                "Day {day} Part {part} ({variant}) is registered more than once, by {}",
+                sources.join(" and ")
+            ),
+        }
+    }
+}
+
+/// Finds gaps and duplicates among the solutions, which must be sorted by day, part and variant
+fn problems(tests: &[&TestRunner]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut puzzles = BTreeMap::<_, Vec<_>>::new();
+    for test in tests {
+        puzzles
+            .entry((test.day, test.part, test.variant))
+            .or_default()
+            .push(test.source);
+    }
+
+    let (Some(first), Some(last)) = (tests.first(), tests.last()) else {
+        return problems;
+    };
+    for day in first.day..=last.day {
+        let registered = |part| {
+            tests
+                .iter()
+                .find(|test| (test.day, test.part) == (day, part))
+        };
+        match (registered(1), registered(2)) {
+            (None, None) => problems.push(Problem::MissingDay(day)),
+            (Some(other), None) if day != LAST_DAY => problems.push(Problem::MissingPart {
+                day,
+                part: 2,
+                other: other.source,
+            }),
+            (None, Some(other)) => problems.push(Problem::MissingPart {
+                day,
+                part: 1,
+                other: other.source,
+            }),
+            _ => {}
+        }
+    }
+
+    for ((day, part, variant), sources) in puzzles {
+        if sources.len() > 1 {
+            problems.push(Problem::Duplicate {
+                day,
+                part,
+                variant: variant.unwrap_or(DEFAULT_VARIANT),
+                sources,
+            });
+        }
+    }
+    problems
+}
+
+impl TestRunner {
+    /// Prints every solution registered for the year with its input, then any gaps or
+    /// duplicates among them. Returns false if there were any
+    pub fn list(year: u16) -> bool {
+        let tests = Self::variants(year);
+        if tests.is_empty() {
+            println!("No puzzles registered for {year}");
+            return true;
+        }
+        let width = tests
+            .iter()
+            .map(|test| test.source.len())
+            .max()
+            .unwrap_or_default();
+        println!(
+            "{:>3} {:>4} {:<10} {:<width$} Input",
+            "Day", "Part", "Variant", "Source"
+        );
+        for test in &tests {
+            let file = InputSource::Default
+                .path(test.file)
+                .map(|path| path.display().to_string())
+                .unwrap_or_else(|| test.file.to_string());
+            let input = match (test.input_size(), test.embedded) {
+                (Some(size), Some(_)) => format!("{file} ({}, embedded)", format_bytes(size)),
+                (Some(size), None) => format!("{file} ({})", format_bytes(size)),
+                (None, _) => format!("{file} (missing)"),
+            };
+            println!(
+                "{:>3} {:>4} {:<10} {:<width$} {input}",
+                test.day,
+                test.part,
+                test.variant.unwrap_or(DEFAULT_VARIANT),
+                test.source
+            );
+        }
+
+        let problems = problems(&tests);
+        for problem in &problems {
+            println!("ERROR: {problem}");
+        }
+        problems.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::TestRunner;
+
+    use super::{problems, Problem};
+
+    #[test]
+    fn diagnostics() {
+        let tests = [
+            TestRunner::test(6, 1, None, "day6::part_1"),
+            TestRunner::test(6, 2, None, "day6::part_2"),
+            TestRunner::test(7, 1, None, "day7::part_1"),
+            TestRunner::test(9, 1, None, "day9::part_1"),
+            TestRunner::test(9, 2, None, "day9::part_2"),
+            TestRunner::test(9, 2, None, "day9_part2::part_2"),
+            TestRunner::test(9, 2, Some("fast"), "day9::fast"),
+        ];
+        let tests = tests.iter().collect::<Vec<_>>();
+        assert_eq!(
+            problems(&tests),
+            vec![
+                Problem::MissingPart {
+                    day: 7,
+                    part: 2,
+                    other: "day7::part_1"
+                },
+                Problem::MissingDay(8),
+                Problem::Duplicate {
+                    day: 9,
+                    part: 2,
+                    variant: "default",
+                    sources: vec!["day9::part_2", "day9_part2::part_2"],
+                },
+            ]
+        );
+        // day 25 only has one part
+        let last = TestRunner::test(25, 1, None, "day25::part_1");
+        assert_eq!(problems(&[&last]), vec![]);
+        assert_eq!(
+            problems(&[tests[3]]),
+            vec![Problem::MissingPart {
+                day: 9,
+                part: 2,
+                other: "day9::part_1"
+            }]
+        );
+    }
+}
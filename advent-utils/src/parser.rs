@@ -0,0 +1,156 @@
+use std::{any::Any, sync::Arc, time::Instant};
+
+use crate::{
+    alloc, catch_panics, cpu, history, spans, timeout::with_timeout, DayParser, Failure,
+    InputSource, ParsedDayPart, PuzzleOutcome, TestRunner, Timeouts,
+};
+
+/// A day's parsed input, with its type erased so it can be handed to either part
+pub(crate) type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// The parse step and solution of a part written against `#[advent_parser]` output
+#[derive(Clone, Copy)]
+pub(crate) struct SharedParse {
+    parse: &'static (dyn Fn(Vec<String>) -> Result<Parsed, String> + Send + Sync),
+    solve: &'static (dyn Fn(&Parsed) -> Result<String, String> + Send + Sync),
+}
+
+impl SharedParse {
+    pub(crate) const fn new<D>() -> Self
+    where
+        D: ParsedDayPart + 'static,
+    {
+        Self {
+            parse: &parse::<D::Parser>,
+            solve: &solve::<D>,
+        }
+    }
+
+    pub(crate) fn parse(&self, lines: Vec<String>) -> Result<Parsed, String> {
+        (self.parse)(lines).map_err(|e| format!("unable to parse input: {e}"))
+    }
+
+    pub(crate) fn solve(&self, parsed: &Parsed) -> Result<String, String> {
+        (self.solve)(parsed)
+    }
+}
+
+fn parse<P>(lines: Vec<String>) -> Result<Parsed, String>
+where
+    P: DayParser,
+{
+    catch_panics(|| P::parse(lines.into_iter())).map(|parsed| Arc::new(parsed) as Parsed)
+}
+
+fn solve<D>(parsed: &Parsed) -> Result<String, String>
+where
+    D: ParsedDayPart,
+{
+    let parsed = parsed
+        .downcast_ref::<<D::Parser as DayParser>::Output>()
+        .ok_or("parsed input is from a different parser")?;
+    catch_panics(|| D::solve(parsed))
+}
+
+impl TestRunner {
+    /// Runs one of the [`groups`], a day's parts sharing a parse step only parse the input once
+    pub(crate) fn run_group(
+        group: &[&'static TestRunner],
+        input: &InputSource,
+        timeouts: &Timeouts,
+    ) -> Vec<PuzzleOutcome> {
+        match group {
+            [test] => vec![test.run_with_timeout(input, timeouts.for_puzzle(test.day, test.part))],
+            day => Self::run_day(day, input, timeouts),
+        }
+    }
+
+    /// Solves parts of the same day that share a parse step, parsing the input only once
+    ///
+    /// The parse step has to finish within every part's timeout, each part then gets what's
+    /// left of its own timeout to solve. Spans and allocations of the parse step aren't
+    /// included in the outcomes
+    pub(crate) fn run_day(
+        tests: &[&'static TestRunner],
+        input: &InputSource,
+        timeouts: &Timeouts,
+    ) -> Vec<PuzzleOutcome> {
+        let Some((first, shared)) = tests.first().and_then(|first| Some((first, first.shared?)))
+        else {
+            return vec![];
+        };
+        let start = Instant::now();
+        let fail_all = |failure: Failure, elapsed| {
+            tests
+                .iter()
+                .map(|test| test.failed(failure.clone(), elapsed))
+                .collect()
+        };
+        let lines = match first.read_input(input) {
+            Ok(lines) => lines,
+            Err(e) => return fail_all(Failure::Error(e), start.elapsed()),
+        };
+        let input_hash = history::hash_input(&lines);
+        let budgets = tests
+            .iter()
+            .map(|test| timeouts.for_puzzle(test.day, test.part))
+            .collect::<Vec<_>>();
+        let parse_timeout = budgets.iter().flatten().min().copied();
+
+        let parse_start = Instant::now();
+        let name = format!("day {} parse", first.day);
+        let parsed = with_timeout(name, parse_timeout, move || {
+            cpu::measure(|| shared.parse(lines))
+        });
+        let parse = parse_start.elapsed();
+        let (parsed, mut parse_cpu) = match parsed {
+            Ok((Ok(parsed), parse_cpu)) => (parsed, parse_cpu),
+            Ok((Err(e), _)) => return fail_all(Failure::Error(e), parse),
+            Err(failure) => return fail_all(failure, parse),
+        };
+
+        tests
+            .iter()
+            .zip(budgets)
+            .map(|(test, budget)| {
+                let Some(shared) = test.shared else {
+                    return test.run(input);
+                };
+                let parsed = parsed.clone();
+                let timeout = budget.map(|budget| budget.saturating_sub(parse));
+                let solve_start = Instant::now();
+                let name = format!("day {} part {}", test.day, test.part);
+                let solved = with_timeout(name, timeout, move || {
+                    cpu::measure(|| spans::collect(|| alloc::measure(|| shared.solve(&parsed))))
+                });
+                match solved {
+                    Ok((((answer, allocations), spans), cpu)) => PuzzleOutcome {
+                        year: test.year,
+                        day: test.day,
+                        part: test.part,
+                        variant: test.variant,
+                        answer: answer.map_err(Failure::Error),
+                        elapsed: solve_start.elapsed(),
+                        parse: Some(parse),
+                        // the parse step is only counted once, with the first part
+                        cpu: cpu.map(|cpu| cpu + parse_cpu.take().unwrap_or_default()),
+                        allocations,
+                        input_hash: Some(input_hash),
+                        spans,
+                    },
+                    Err(failure) => test.failed(failure, timeout.unwrap_or(solve_start.elapsed())),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits puzzles into the groups they're run in, parts sharing a parse step are grouped by day
+/// and every other puzzle runs on its own
+pub(crate) fn groups<'a>(tests: &'a [&'static TestRunner]) -> Vec<&'a [&'static TestRunner]> {
+    tests
+        .chunk_by(|a, b| {
+            (a.year, a.day) == (b.year, b.day) && a.shared.is_some() && b.shared.is_some()
+        })
+        .collect()
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+/// Runs `work` over every item on up to `jobs` threads
+///
+/// `done` is called on the calling thread with each result in the same order as `items`, as soon
+/// as every earlier item has also finished.
+pub(crate) fn run_in_order<T, R>(
+    items: &[T],
+    jobs: usize,
+    work: impl Fn(&T) -> R + Sync,
+    mut done: impl FnMut(R),
+) where
+    T: Sync,
+    R: Send,
+{
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            let (next, work) = (&next, &work);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                if sender.send((index, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+        let mut finished = BTreeMap::new();
+        let mut expected = 0;
+        for (index, result) in receiver {
+            finished.insert(index, result);
+            while let Some(result) = finished.remove(&expected) {
+                done(result);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::{thread, time::Duration};
+
+    use super::run_in_order;
+
+    #[test]
+    fn keeps_order() {
+        let items = [30, 0, 20, 10, 0];
+        let mut results = vec![];
+        run_in_order(
+            &items,
+            4,
+            |millis| {
+                thread::sleep(Duration::from_millis(*millis));
+                *millis
+            },
+            |result| results.push(result),
+        );
+        assert_eq!(results, items);
+    }
+}
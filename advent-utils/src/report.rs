@@ -0,0 +1,266 @@
+use std::{fmt::Write, str::FromStr, time::Duration};
+
+use serde_json::json;
+
+use crate::{print_summary, Failure, PuzzleOutcome, Span};
+
+/// How the results of a run are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Human readable lines, printed as each puzzle finishes
+    #[default]
+    Text,
+    Json,
+    /// JUnit XML, for test report viewers
+    Junit,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "junit" => Ok(Self::Junit),
+            s => Err(format!(
+                "unknown format {s}, expected one of text, json, junit"
+            )),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// Called as each puzzle finishes, only text output is streamed
+    pub(crate) fn outcome(self, outcome: &PuzzleOutcome) {
+        if self == Self::Text {
+            outcome.print();
+        }
+    }
+
+    /// Called once every puzzle has finished, `wall_time` is `None` for a single puzzle run
+    pub(crate) fn finish(self, outcomes: &[PuzzleOutcome], wall_time: Option<Duration>) {
+        match self {
+            Self::Text => {
+                if let Some(wall_time) = wall_time {
+                    print_summary(outcomes);
+                    // failed puzzles and threads the solutions spawn aren't counted
+                    let cpu_time = outcomes
+                        .iter()
+                        .filter_map(|outcome| outcome.cpu)
+                        .reduce(|total, cpu| total + cpu);
+                    match cpu_time {
+                        Some(cpu_time) => {
+                            println!("Wall time: {wall_time:?} CPU time: {cpu_time:?}")
+                        }
+                        None => println!("Wall time: {wall_time:?}"),
+                    }
+                }
+            }
+            Self::Json => println!("{}", json_report(outcomes, wall_time)),
+            Self::Junit => print!("{}", junit_report(outcomes, wall_time)),
+        }
+    }
+}
+
+impl PuzzleOutcome {
+    fn status(&self) -> &'static str {
+        match self.answer {
+            Ok(_) => "passed",
+            Err(Failure::Error(_)) => "failed",
+            Err(Failure::Timeout(_)) => "timeout",
+        }
+    }
+}
+
+fn json_report(outcomes: &[PuzzleOutcome], wall_time: Option<Duration>) -> String {
+    let results = outcomes
+        .iter()
+        .map(|outcome| {
+            json!({
+                "year": outcome.year,
+                "day": outcome.day,
+                "part": outcome.part,
+                "variant": outcome.variant,
+                "status": outcome.status(),
+                "answer": outcome.answer.as_ref().ok(),
+                "duration_secs": outcome.elapsed.as_secs_f64(),
+                "parse_secs": outcome.parse.map(|parse| parse.as_secs_f64()),
+                "cpu_secs": outcome.cpu.map(|cpu| cpu.as_secs_f64()),
+                "spans": spans_json(&outcome.spans),
+                "allocations": outcome.allocations.map(|allocations| json!({
+                    "count": allocations.count,
+                    "bytes": allocations.bytes,
+                    "peak_bytes": allocations.peak,
+                })),
+                "error": outcome.answer.as_ref().err().map(ToString::to_string),
+            })
+        })
+        .collect::<Vec<_>>();
+    let count = |status| {
+        outcomes
+            .iter()
+            .filter(|outcome| outcome.status() == status)
+            .count()
+    };
+    json!({
+        "results": results,
+        "passed": count("passed"),
+        "failed": count("failed"),
+        "timed_out": count("timeout"),
+        "wall_time_secs": wall_time.map(|time| time.as_secs_f64()),
+    })
+    .to_string()
+}
+
+fn spans_json(spans: &[Span]) -> serde_json::Value {
+    spans
+        .iter()
+        .map(|span| {
+            json!({
+                "name": span.name,
+                "duration_secs": span.elapsed.as_secs_f64(),
+                "calls": span.calls,
+                "children": spans_json(&span.children),
+            })
+        })
+        .collect()
+}
+
+fn junit_report(outcomes: &[PuzzleOutcome], wall_time: Option<Duration>) -> String {
+    let failures = outcomes
+        .iter()
+        .filter(|outcome| outcome.answer.is_err())
+        .count();
+    let time = wall_time
+        .unwrap_or_else(|| outcomes.iter().map(|outcome| outcome.elapsed).sum())
+        .as_secs_f64();
+    let mut xml = String::new();
+    // writing to a string can't fail
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(
+        xml,
+        r#"<testsuites name="advent-of-code" tests="{}" failures="0" errors="{failures}" time="{time}">"#,
+        outcomes.len()
+    );
+    let _ = writeln!(
+        xml,
+        r#"  <testsuite name="advent-of-code" tests="{}" failures="0" errors="{failures}" time="{time}">"#,
+        outcomes.len()
+    );
+    for outcome in outcomes {
+        let _ = writeln!(
+            xml,
+            r#"    <testcase classname="day{}" name="part{}{}" time="{}">"#,
+            outcome.day,
+            outcome.part,
+            outcome
+                .variant
+                .map(|variant| format!(" ({})", escape(variant)))
+                .unwrap_or_default(),
+            outcome.elapsed.as_secs_f64()
+        );
+        match &outcome.answer {
+            Ok(answer) => {
+                let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(answer));
+            }
+            Err(failure) => {
+                let _ = writeln!(
+                    xml,
+                    r#"      <error type="{}" message="{}"/>"#,
+                    outcome.status(),
+                    escape(&failure.to_string())
+                );
+            }
+        }
+        let _ = writeln!(xml, "    </testcase>");
+    }
+    let _ = writeln!(xml, "  </testsuite>");
+    let _ = writeln!(xml, "</testsuites>");
+    xml
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::{AllocStats, Failure, PuzzleOutcome, Span};
+
+    use super::{json_report, junit_report};
+
+    fn outcomes() -> Vec<PuzzleOutcome> {
+        vec![
+            PuzzleOutcome {
+                elapsed: Duration::from_millis(2),
+                parse: Some(Duration::from_millis(1)),
+                cpu: Some(Duration::from_millis(2)),
+                allocations: Some(AllocStats {
+                    count: 3,
+                    bytes: 64,
+                    peak: 32,
+                }),
+                spans: vec![Span {
+                    name: "parse",
+                    elapsed: Duration::from_millis(1),
+                    calls: 1,
+                    children: vec![],
+                }],
+                ..PuzzleOutcome::test(1, 1, Ok("142"))
+            },
+            PuzzleOutcome {
+                elapsed: Duration::from_millis(1),
+                ..PuzzleOutcome::test(8, 1, Err("unknown node <AAA>"))
+            },
+            PuzzleOutcome {
+                answer: Err(Failure::Timeout(Duration::from_secs(1))),
+                elapsed: Duration::from_secs(1),
+                ..PuzzleOutcome::test(8, 2, Ok(""))
+            },
+        ]
+    }
+
+    #[test]
+    fn json() {
+        let report: serde_json::Value =
+            serde_json::from_str(&json_report(&outcomes(), None)).unwrap();
+        assert_eq!(report["passed"], 1);
+        assert_eq!(report["failed"], 1);
+        assert_eq!(report["timed_out"], 1);
+        assert_eq!(report["results"][0]["year"], 2023);
+        assert_eq!(report["results"][0]["answer"], "142");
+        assert_eq!(report["results"][0]["allocations"]["peak_bytes"], 32);
+        assert_eq!(report["results"][0]["parse_secs"], 0.001);
+        assert!(report["results"][1]["parse_secs"].is_null());
+        assert_eq!(report["results"][0]["cpu_secs"], 0.002);
+        assert!(report["results"][1]["cpu_secs"].is_null());
+        assert!(report["results"][1]["allocations"].is_null());
+        assert_eq!(report["results"][0]["spans"][0]["name"], "parse");
+        assert_eq!(report["results"][1]["status"], "failed");
+        assert_eq!(report["results"][1]["error"], "unknown node <AAA>");
+        assert_eq!(report["results"][2]["status"], "timeout");
+    }
+
+    #[test]
+    fn junit() {
+        let report = junit_report(&outcomes(), Some(Duration::from_secs(2)));
+        assert!(report.contains(r#"tests="3" failures="0" errors="2" time="2""#));
+        assert!(report.contains("<system-out>142</system-out>"));
+        assert!(report.contains(r#"<error type="failed" message="unknown node &lt;AAA&gt;"/>"#));
+        assert!(report.contains(r#"<error type="timeout" message="TIMEOUT after 1s"/>"#));
+    }
+}
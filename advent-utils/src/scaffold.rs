@@ -0,0 +1,157 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{TestRunner, DEFAULT_YEAR};
+
+const TEMPLATE: &str = r#"use advent::advent_of_code;
+use advent_utils::*;
+
+#[advent_of_code({year}day = {day}, part = 1)]
+fn part_1(lines: impl Iterator<Item = String>) -> String {
+    todo!("day {day} part 1 with {} lines", lines.count())
+}
+
+#[advent_of_code({year}day = {day}, part = 2)]
+fn part_2(lines: impl Iterator<Item = String>) -> String {
+    todo!("day {day} part 2 with {} lines", lines.count())
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn part_1() {
+        let sample = "";
+        assert_eq!(super::part_1(sample.lines().map(|l| l.to_string())), "");
+    }
+}
+"#;
+
+/// Files written when scaffolding a day
+#[derive(Debug, PartialEq)]
+pub struct Scaffolded {
+    pub module: PathBuf,
+    /// `None` when the input file already existed
+    pub input: Option<PathBuf>,
+}
+
+/// Writes `src_dir/dayN.rs` from the template and creates an empty input file, refusing to touch
+/// a day that already exists or isn't part of the event. The build script picks the new module
+/// up, no declaration needed
+pub fn scaffold_day(
+    year: u16,
+    day: u8,
+    src_dir: &Path,
+    input: &Path,
+) -> Result<Scaffolded, String> {
+    // the module wouldn't get past `#[advent_of_code]`, breaking the build of every other day
+    if !(1..=25).contains(&day) {
+        return Err(format!("day must be between 1 and 25, found {day}"));
+    }
+    let module = src_dir.join(format!("day{day}.rs"));
+    let source = TEMPLATE
+        .replace(
+            "{year}",
+            &match year {
+                DEFAULT_YEAR => String::new(),
+                year => format!("year = {year}, "),
+            },
+        )
+        .replace("{day}", &day.to_string());
+    fs::create_dir_all(src_dir)
+        .map_err(|e| format!("unable to create {}: {e}", src_dir.display()))?;
+
+    // create_new so existing work is never overwritten
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&module)
+        .and_then(|mut file| file.write_all(source.as_bytes()))
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => format!("{} already exists", module.display()),
+            _ => format!("unable to write {}: {e}", module.display()),
+        })?;
+
+    let input = if input.exists() {
+        None
+    } else {
+        if let Some(parent) = input.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("unable to create {}: {e}", parent.display()))?;
+        }
+        fs::write(input, "").map_err(|e| format!("unable to write {}: {e}", input.display()))?;
+        Some(input.to_path_buf())
+    };
+    Ok(Scaffolded { module, input })
+}
+
+impl TestRunner {
+    /// Scaffolds a new day in `./src`, or `./src/year<year>` for any year but the default,
+    /// returns false if nothing was created
+    pub fn new_day(year: u16, day: u8) -> bool {
+        let src_dir = match year {
+            DEFAULT_YEAR => PathBuf::from("./src"),
+            year => PathBuf::from(format!("./src/year{year}")),
+        };
+        match scaffold_day(year, day, &src_dir, &Self::input_path(year, day, None)) {
+            Ok(Scaffolded { module, input }) => {
+                println!("Created {}", module.display());
+                if let Some(input) = input {
+                    println!("Created {}", input.display());
+                }
+                true
+            }
+            Err(e) => {
+                println!("Unable to create day {day}: {e}");
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use crate::TempDir;
+
+    use super::{scaffold_day, Scaffolded};
+
+    #[test]
+    fn refuses_to_overwrite() {
+        let dir = TempDir::new("scaffold");
+        let src = dir.join("src");
+        fs::create_dir_all(&src).unwrap();
+        let input = dir.join("test_data").join("day_9");
+
+        assert_eq!(
+            scaffold_day(2023, 9, &src, &input),
+            Ok(Scaffolded {
+                module: src.join("day9.rs"),
+                input: Some(input.clone()),
+            })
+        );
+        assert!(fs::read_to_string(src.join("day9.rs"))
+            .unwrap()
+            .contains("#[advent_of_code(day = 9, part = 1)]"));
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+
+        fs::write(src.join("day9.rs"), "my work").unwrap();
+        assert!(scaffold_day(2023, 9, &src, &input).is_err());
+        assert_eq!(fs::read_to_string(src.join("day9.rs")).unwrap(), "my work");
+
+        let year_src = src.join("year2022");
+        let year_input = dir.join("test_data").join("2022").join("day_9");
+        assert!(scaffold_day(2022, 9, &year_src, &year_input).is_ok());
+        assert!(fs::read_to_string(year_src.join("day9.rs"))
+            .unwrap()
+            .contains("#[advent_of_code(year = 2022, day = 9, part = 1)]"));
+
+        for day in [0, 26] {
+            assert!(scaffold_day(2023, day, &src, &input).is_err());
+            assert!(!src.join(format!("day{day}.rs")).exists());
+        }
+    }
+}
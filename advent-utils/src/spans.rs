@@ -0,0 +1,202 @@
+use std::{
+    cell::RefCell,
+    time::{Duration, Instant},
+};
+
+/// Time spent in a named phase of a solution, with the phases nested inside it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub name: &'static str,
+    /// Total time across every call
+    pub elapsed: Duration,
+    /// Number of times the phase was entered
+    pub calls: usize,
+    pub children: Vec<Span>,
+}
+
+/// Spans recorded so far on a thread, kept flat with indices so guards can find their node
+struct Collector {
+    nodes: Vec<Node>,
+    /// Nodes of the spans currently open, innermost last
+    open: Vec<usize>,
+}
+
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    elapsed: Duration,
+    calls: usize,
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Option<Collector>> = const { RefCell::new(None) };
+}
+
+/// Marks a phase of a solution until the returned guard is dropped, see [`span!`](crate::span)
+///
+/// Phases with the same name under the same parent are added together. Only spans on the thread
+/// solving the puzzle are collected, anywhere else this does nothing
+pub fn span(name: &'static str) -> SpanGuard {
+    let node = COLLECTOR.with_borrow_mut(|collector| {
+        let collector = collector.as_mut()?;
+        let parent = collector.open.last().copied();
+        let node = match collector
+            .nodes
+            .iter()
+            .position(|node| node.parent == parent && node.name == name)
+        {
+            Some(node) => node,
+            None => {
+                collector.nodes.push(Node {
+                    name,
+                    parent,
+                    elapsed: Duration::ZERO,
+                    calls: 0,
+                });
+                collector.nodes.len() - 1
+            }
+        };
+        collector.nodes[node].calls += 1;
+        collector.open.push(node);
+        Some(node)
+    });
+    SpanGuard {
+        node,
+        start: Instant::now(),
+    }
+}
+
+/// Times the rest of the enclosing block as a named phase
+///
+/// ```
+/// use advent_utils::span;
+///
+/// fn part_1(lines: impl Iterator<Item = String>) -> usize {
+///     let numbers = {
+///         span!("parse");
+///         lines.map(|line| line.len()).collect::<Vec<_>>()
+///     };
+///     span!("sum");
+///     numbers.iter().sum()
+/// }
+/// # assert_eq!(part_1(["ab".to_string()].into_iter()), 2);
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        let _span = $crate::span($name);
+    };
+}
+
+/// Ends its span when dropped
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct SpanGuard {
+    node: Option<usize>,
+    start: Instant,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let Some(node) = self.node else {
+            return;
+        };
+        let elapsed = self.start.elapsed();
+        // try_with as a guard could be dropped while the thread is shutting down
+        let _ = COLLECTOR.try_with(|collector| {
+            if let Some(collector) = collector.borrow_mut().as_mut() {
+                collector.nodes[node].elapsed += elapsed;
+                // guards dropped out of order close everything opened after them too
+                if let Some(position) = collector.open.iter().rposition(|&open| open == node) {
+                    collector.open.truncate(position);
+                }
+            }
+        });
+    }
+}
+
+/// Runs `f`, collecting every span it opens on this thread into a tree
+pub(crate) fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<Span>) {
+    let previous = COLLECTOR.replace(Some(Collector {
+        nodes: vec![],
+        open: vec![],
+    }));
+    let result = f();
+    let collector = COLLECTOR.replace(previous);
+    let spans = collector.map_or_else(Vec::new, |collector| tree(&collector.nodes, None));
+    (result, spans)
+}
+
+fn tree(nodes: &[Node], parent: Option<usize>) -> Vec<Span> {
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, node)| node.parent == parent)
+        .map(|(index, node)| Span {
+            name: node.name,
+            elapsed: node.elapsed,
+            calls: node.calls,
+            children: tree(nodes, Some(index)),
+        })
+        .collect()
+}
+
+/// Prints spans as an indented tree, with each span's share of the puzzle's time
+pub(crate) fn print_spans(spans: &[Span], total: Duration, depth: usize) {
+    for span in spans {
+        let share = span.elapsed.as_secs_f64() / total.as_secs_f64() * 100.0;
+        let calls = if span.calls > 1 {
+            format!(" x{}", span.calls)
+        } else {
+            String::new()
+        };
+        println!(
+            "{:indent$}{} {:.2?} ({share:.1}%){calls}",
+            "",
+            span.name,
+            span.elapsed,
+            indent = depth * 2 + 2
+        );
+        print_spans(&span.children, total, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{collect, span, Span};
+
+    /// Flattens the tree into `path calls` entries
+    fn paths(spans: &[Span], prefix: &str) -> Vec<String> {
+        spans
+            .iter()
+            .flat_map(|span| {
+                let path = format!("{prefix}{}", span.name);
+                [format!("{path} {}", span.calls)]
+                    .into_iter()
+                    .chain(paths(&span.children, &format!("{path}/")))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nesting() {
+        let (answer, spans) = collect(|| {
+            let parse = span("parse");
+            for _ in 0..3 {
+                crate::span!("line");
+            }
+            drop(parse);
+            crate::span!("solve");
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(
+            paths(&spans, ""),
+            vec!["parse 1", "parse/line 3", "solve 1"]
+        );
+        assert!(spans[0].elapsed >= spans[0].children[0].elapsed);
+
+        // nothing is collected outside of a run
+        let _ignored = span("outside");
+        assert_eq!(collect(|| ()).1, vec![]);
+    }
+}
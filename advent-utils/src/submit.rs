@@ -0,0 +1,372 @@
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{
+    answers::{escape, unescape},
+    AocClient, InputSource, TestRunner,
+};
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint of which direction
+    Wrong,
+    /// An answer was submitted too recently, nothing was checked
+    Wait(Duration),
+    /// The part was already solved, nothing was checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict out of the page returned after submitting
+    fn parse(page: &str) -> Option<Self> {
+        let article = page
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(page, |(article, _)| article);
+        if article.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if article.contains("That's not the right answer") {
+            if article.contains("too high") {
+                Some(Self::TooHigh)
+            } else if article.contains("too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Wrong)
+            }
+        } else if article.contains("You gave an answer too recently") {
+            Some(Self::Wait(parse_wait(article).unwrap_or_default()))
+        } else if article.contains("You don't seem to be solving the right level") {
+            Some(Self::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the answer was actually checked, only these are worth remembering
+    fn is_final(&self) -> bool {
+        !matches!(self, Self::Wait(_) | Self::AlreadySolved)
+    }
+}
+
+/// Reads "You have 1m 5s left to wait" into a duration
+fn parse_wait(article: &str) -> Option<Duration> {
+    let (_, rest) = article.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|part| {
+            let (amount, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let amount = amount.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(amount * 60 * 60),
+                "m" => Some(amount * 60),
+                "s" => Some(amount),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too_high"),
+            Verdict::TooLow => write!(f, "too_low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(wait) => write!(f, "wait {wait:?}"),
+            Verdict::AlreadySolved => write!(f, "already_solved"),
+        }
+    }
+}
+
+/// A checked answer from the submissions file
+#[derive(Debug, Clone, PartialEq)]
+struct Attempt {
+    year: u16,
+    day: u8,
+    part: u8,
+    verdict: Verdict,
+    answer: String,
+}
+
+impl FromStr for Attempt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.splitn(5, ' ');
+        let mut next = |name| {
+            fields
+                .next()
+                .ok_or_else(|| format!("missing {name} in {s}"))
+        };
+        let year = next("year")?;
+        let day = next("day")?;
+        let part = next("part")?;
+        let verdict = match next("verdict")? {
+            "correct" => Verdict::Correct,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "wrong" => Verdict::Wrong,
+            verdict => return Err(format!("unknown verdict {verdict} in {s}")),
+        };
+        Ok(Self {
+            year: year.parse().map_err(|e| format!("bad year {year}: {e}"))?,
+            day: day.parse().map_err(|e| format!("bad day {day}: {e}"))?,
+            part: part.parse().map_err(|e| format!("bad part {part}: {e}"))?,
+            verdict,
+            answer: unescape(next("answer")?),
+        })
+    }
+}
+
+/// Every answer submitted so far, one `year day part verdict answer` line each with the answer
+/// escaped like in the answers file
+#[derive(Debug, Default)]
+struct Submissions {
+    attempts: Vec<Attempt>,
+}
+
+impl Submissions {
+    fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e),
+        };
+        let attempts = data
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self { attempts })
+    }
+
+    fn record(&mut self, path: &Path, attempt: Attempt) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let Attempt {
+            year,
+            day,
+            part,
+            verdict,
+            answer,
+        } = &attempt;
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{year} {day} {part} {verdict} {}", escape(answer))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Returns why `answer` shouldn't be submitted, if what we already know rules it out
+    fn check(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<(), String> {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|attempt| attempt.year == year && attempt.day == day && attempt.part == part);
+        let number = answer.parse::<i128>().ok();
+        for attempt in attempts {
+            let known = attempt.answer.parse::<i128>().ok();
+            match (&attempt.verdict, number, known) {
+                (Verdict::Correct, _, _) if attempt.answer == answer => {
+                    return Err(format!("{answer} was already accepted as correct"))
+                }
+                (Verdict::Correct, _, _) => {
+                    return Err(format!(
+                        "already solved with {}, {answer} can't be right",
+                        attempt.answer
+                    ))
+                }
+                (verdict, _, _) if attempt.answer == answer => {
+                    return Err(format!("{answer} was already submitted and was {verdict}"))
+                }
+                (Verdict::TooHigh, Some(number), Some(known)) if number >= known => {
+                    return Err(format!(
+                        "{answer} can't be right, {known} was already too high"
+                    ))
+                }
+                (Verdict::TooLow, Some(number), Some(known)) if number <= known => {
+                    return Err(format!(
+                        "{answer} can't be right, {known} was already too low"
+                    ))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AocClient {
+    fn submissions_file(&self) -> PathBuf {
+        self.state_dir.join("submissions")
+    }
+
+    /// Posts an answer and reads the site's verdict
+    pub fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+        let url = format!("{}/{}/day/{day}/answer", self.base_url, self.year);
+        let page = self
+            .request(
+                ureq::post(&url),
+                &[("level", &part.to_string()), ("answer", answer)],
+            )?
+            .into_string()
+            .map_err(|e| format!("unable to read response from {url}: {e}"))?;
+        Verdict::parse(&page).ok_or_else(|| format!("unrecognised response from {url}"))
+    }
+}
+
+impl TestRunner {
+    /// Solves the puzzle and submits the answer, unless an earlier attempt already rules it
+    /// out. Returns true if the answer was correct
+    pub fn submit(client: &AocClient, day: u8, part: u8) -> bool {
+        let test = match Self::find(client.year, day, part, None) {
+            Ok(test) => test,
+            Err(e) => {
+                eprintln!("{e}");
+                return false;
+            }
+        };
+        let answer = match test.run(&InputSource::Default).answer {
+            Ok(answer) => answer,
+            Err(failure) => {
+                println!("Day {day} Part {part}: FAILED: {failure}");
+                return false;
+            }
+        };
+        match submit(client, day, part, &answer) {
+            Ok(verdict) => {
+                println!("Day {day} Part {part}: {answer} is {verdict}");
+                verdict == Verdict::Correct
+            }
+            Err(e) => {
+                println!("Day {day} Part {part}: not submitting {answer}: {e}");
+                false
+            }
+        }
+    }
+}
+
+fn submit(client: &AocClient, day: u8, part: u8, answer: &str) -> Result<Verdict, String> {
+    let file = client.submissions_file();
+    let mut submissions =
+        Submissions::load(&file).map_err(|e| format!("unable to read {}: {e}", file.display()))?;
+    submissions.check(client.year, day, part, answer)?;
+    let verdict = client.submit_answer(day, part, answer)?;
+    if verdict.is_final() {
+        let attempt = Attempt {
+            year: client.year,
+            day,
+            part,
+            verdict: verdict.clone(),
+            answer: answer.to_string(),
+        };
+        submissions.record(&file, attempt).map_err(|e| {
+            format!(
+                "{verdict}, but unable to record it in {}: {e}",
+                file.display()
+            )
+        })?;
+    }
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, time::Duration};
+
+    use crate::{client::stub, AocClient, TempDir};
+
+    use super::{submit, Attempt, Submissions, Verdict};
+
+    const TOO_HIGH: &str =
+        "<main><article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data.</p></article></main>";
+    const RIGHT: &str = "<main><article><p>That's the right answer! You are one gold star closer \
+        to restoring snow operations.</p></article></main>";
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(Verdict::parse(TOO_HIGH), Some(Verdict::TooHigh));
+        assert_eq!(Verdict::parse(RIGHT), Some(Verdict::Correct));
+        assert_eq!(
+            Verdict::parse(
+                "<article><p>You gave an answer too recently; you have to wait after submitting \
+                an answer before trying again.  You have 1m 5s left to wait.</p></article>"
+            ),
+            Some(Verdict::Wait(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            Verdict::parse(
+                "<article><p>You don't seem to be solving the right level.  Did you already \
+                complete it?</p></article>"
+            ),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(Verdict::parse("<html>login</html>"), None);
+    }
+
+    #[test]
+    fn remembers_attempts() {
+        let (base_url, requests) = stub::serve(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let dir = TempDir::new("submit");
+        let client = AocClient {
+            base_url,
+            session: "secret".to_string(),
+            year: 2023,
+            throttle: Duration::ZERO,
+            state_dir: dir.to_path_buf(),
+        };
+        assert_eq!(submit(&client, 8, 1, "500"), Ok(Verdict::TooHigh));
+        let request = requests.recv().unwrap();
+        assert_eq!(request.request_line, "POST /2023/day/8/answer HTTP/1.1");
+        assert_eq!(request.body, "level=1&answer=500");
+
+        // neither of these reach the server
+        assert!(submit(&client, 8, 1, "500")
+            .unwrap_err()
+            .contains("already submitted"));
+        assert!(submit(&client, 8, 1, "501")
+            .unwrap_err()
+            .contains("too high"));
+
+        assert_eq!(submit(&client, 8, 1, "2"), Ok(Verdict::Correct));
+        assert_eq!(requests.recv().unwrap().body, "level=1&answer=2");
+        assert!(submit(&client, 8, 1, "3")
+            .unwrap_err()
+            .contains("already solved"));
+        assert_eq!(
+            fs::read_to_string(dir.join("submissions")).unwrap(),
+            "2023 8 1 too_high 500\n2023 8 1 correct 2\n"
+        );
+    }
+
+    #[test]
+    fn multi_line_answers() {
+        let dir = TempDir::new("submissions");
+        let path = dir.join("submissions");
+        let attempt = Attempt {
+            year: 2022,
+            day: 10,
+            part: 2,
+            verdict: Verdict::Wrong,
+            answer: "#..#\n####".to_string(),
+        };
+        Submissions::default().record(&path, attempt).unwrap();
+        let submissions = Submissions::load(&path).unwrap();
+        assert_eq!(submissions.attempts.len(), 1);
+        assert_eq!(submissions.attempts[0].answer, "#..#\n####");
+    }
+}
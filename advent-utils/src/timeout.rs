@@ -0,0 +1,136 @@
+use std::{str::FromStr, sync::mpsc, thread, time::Duration};
+
+use crate::{Failure, InputSource, PuzzleOutcome, TestRunner};
+
+/// How long puzzles may run before they are reported as timed out
+#[derive(Debug, Clone, Default)]
+pub struct Timeouts {
+    /// Applies to every puzzle without a more specific timeout
+    pub global: Option<Duration>,
+    pub puzzles: Vec<PuzzleTimeout>,
+}
+
+impl Timeouts {
+    /// The most specific timeout for a puzzle, a day/part entry wins over a whole day entry
+    pub fn for_puzzle(&self, day: u8, part: u8) -> Option<Duration> {
+        let find = |part| {
+            self.puzzles
+                .iter()
+                .rev()
+                .find(|timeout| timeout.day == day && timeout.part == part)
+        };
+        find(Some(part))
+            .or_else(|| find(None))
+            .map(|timeout| timeout.timeout)
+            .or(self.global)
+    }
+}
+
+/// Timeout for a single day or day/part, written as `day=seconds` or `day:part=seconds`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PuzzleTimeout {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub timeout: Duration,
+}
+
+impl FromStr for PuzzleTimeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (puzzle, timeout) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected day[:part]=seconds, got {s}"))?;
+        let (day, part) = match puzzle.split_once(':') {
+            Some((day, part)) => (day, Some(part)),
+            None => (puzzle, None),
+        };
+        Ok(Self {
+            day: day.parse().map_err(|e| format!("bad day {day}: {e}"))?,
+            part: part
+                .map(|part| part.parse().map_err(|e| format!("bad part {part}: {e}")))
+                .transpose()?,
+            timeout: parse_seconds(timeout)?,
+        })
+    }
+}
+
+/// Parses a possibly fractional number of seconds
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds = s
+        .parse::<f64>()
+        .map_err(|e| format!("bad number of seconds {s}: {e}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("bad number of seconds {s}: {e}"))
+}
+
+/// Runs `f` on a separate thread named `name`, giving up on it once the timeout passes. Without
+/// a timeout `f` runs on the current thread
+///
+/// A thread can't be killed, so a timed out `f` keeps running in the background until the
+/// process exits
+pub(crate) fn with_timeout<R: Send + 'static>(
+    name: String,
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, Failure> {
+    let Some(timeout) = timeout else {
+        return Ok(f());
+    };
+    let (sender, receiver) = mpsc::channel();
+    let spawned = thread::Builder::new().name(name).spawn(move || {
+        // the receiver is gone once the timeout has passed, so the result is dropped
+        let _ = sender.send(f());
+    });
+    match spawned {
+        Ok(_) => match receiver.recv_timeout(timeout) {
+            Ok(result) => Ok(result),
+            Err(mpsc::RecvTimeoutError::Timeout) => Err(Failure::Timeout(timeout)),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(Failure::Error(
+                "puzzle thread exited without an answer".to_string(),
+            )),
+        },
+        Err(e) => Err(Failure::Error(format!(
+            "unable to start puzzle thread: {e}"
+        ))),
+    }
+}
+
+impl TestRunner {
+    /// Runs the puzzle on a separate thread, giving up on it once the timeout passes
+    pub(crate) fn run_with_timeout(
+        &'static self,
+        input: &InputSource,
+        timeout: Option<Duration>,
+    ) -> PuzzleOutcome {
+        let input = input.clone();
+        let name = format!("day {} part {}", self.day, self.part);
+        with_timeout(name, timeout, move || self.run(&input))
+            .unwrap_or_else(|failure| self.failed(failure, timeout.unwrap_or_default()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{PuzzleTimeout, Timeouts};
+
+    #[test]
+    fn most_specific() {
+        let timeouts = Timeouts {
+            global: Some(Duration::from_secs(10)),
+            puzzles: vec![
+                "5=60".parse().unwrap(),
+                "5:2=120".parse().unwrap(),
+                "8:1=0.5".parse().unwrap(),
+            ],
+        };
+        assert_eq!(timeouts.for_puzzle(5, 1), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.for_puzzle(5, 2), Some(Duration::from_secs(120)));
+        assert_eq!(timeouts.for_puzzle(8, 1), Some(Duration::from_millis(500)));
+        assert_eq!(timeouts.for_puzzle(8, 2), Some(Duration::from_secs(10)));
+        assert_eq!(Timeouts::default().for_puzzle(1, 1), None);
+        assert!("5:2".parse::<PuzzleTimeout>().is_err());
+        assert!("5:x=1".parse::<PuzzleTimeout>().is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{history, parser, pool, Answers, InputSource, PuzzleOutcome, TestRunner, Timeouts};
+
+/// Days per row of the calendar
+const WEEK: u8 = 5;
+const DAYS: u8 = 25;
+
+/// What the dashboard knows about one part of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PartState {
+    /// No solution is registered
+    Missing,
+    NoInput,
+    /// Solution and input are there but it hasn't been run yet
+    Ready,
+    Running,
+    /// Solved, matching the known answer if there is one
+    Passed,
+    /// Solved, but with a different answer than the known one
+    Wrong,
+    Failed,
+}
+
+impl PartState {
+    fn symbol(self) -> (char, Color) {
+        match self {
+            PartState::Missing => (' ', Color::Reset),
+            PartState::NoInput => ('.', Color::DarkGrey),
+            PartState::Ready => ('o', Color::Reset),
+            PartState::Running => ('~', Color::Yellow),
+            PartState::Passed => ('*', Color::Green),
+            PartState::Wrong => ('x', Color::Red),
+            PartState::Failed => ('!', Color::Red),
+        }
+    }
+}
+
+/// What a key press asks the dashboard to do
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Move(i8),
+    RunSelected,
+    RunAll,
+    Quit,
+    Nothing,
+}
+
+impl From<KeyEvent> for Action {
+    fn from(key: KeyEvent) -> Self {
+        match key.code {
+            KeyCode::Left | KeyCode::Char('h') => Action::Move(-1),
+            KeyCode::Right | KeyCode::Char('l') => Action::Move(1),
+            KeyCode::Up | KeyCode::Char('k') => Action::Move(-(WEEK as i8)),
+            KeyCode::Down | KeyCode::Char('j') => Action::Move(WEEK as i8),
+            KeyCode::Enter | KeyCode::Char('r') => Action::RunSelected,
+            KeyCode::Char('a') => Action::RunAll,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+            _ => Action::Nothing,
+        }
+    }
+}
+
+struct Dashboard {
+    year: u16,
+    /// Day the details are shown for
+    selected: u8,
+    tests: BTreeMap<(u8, u8), &'static TestRunner>,
+    answers: Answers,
+    /// Latest time recorded in the history file
+    recorded: BTreeMap<(u8, u8), Duration>,
+    /// Results from this session
+    results: BTreeMap<(u8, u8), PuzzleOutcome>,
+    running: BTreeSet<(u8, u8)>,
+    history: Option<PathBuf>,
+    timeouts: Timeouts,
+}
+
+impl Dashboard {
+    fn part_state(&self, day: u8, part: u8) -> PartState {
+        let Some(test) = self.tests.get(&(day, part)) else {
+            return PartState::Missing;
+        };
+        if self.running.contains(&(day, part)) {
+            return PartState::Running;
+        }
+        match self
+            .results
+            .get(&(day, part))
+            .map(|outcome| &outcome.answer)
+        {
+            Some(Ok(answer)) => match self.answers.get(day, part) {
+                Some(known) if known != answer => PartState::Wrong,
+                _ => PartState::Passed,
+            },
+            Some(Err(_)) => PartState::Failed,
+            None if test.input_size().is_none() => PartState::NoInput,
+            None => PartState::Ready,
+        }
+    }
+
+    fn select(&mut self, offset: i8) {
+        let day = self.selected as i8 + offset;
+        if (1..=DAYS as i8).contains(&day) {
+            self.selected = day as u8;
+        }
+    }
+
+    /// Starts the puzzles on a background thread, their outcomes arrive on `results`
+    fn run(&mut self, tests: Vec<&'static TestRunner>, results: &Sender<PuzzleOutcome>) {
+        let tests = tests
+            .into_iter()
+            .filter(|test| self.running.insert((test.day, test.part)))
+            .collect::<Vec<_>>();
+        let results = results.clone();
+        let jobs = thread::available_parallelism().map_or(1, |jobs| jobs.get());
+        let timeouts = self.timeouts.clone();
+        thread::spawn(move || {
+            pool::run_in_order(
+                &parser::groups(&tests),
+                jobs,
+                |group| TestRunner::run_group(group, &InputSource::Default, &timeouts),
+                |group| {
+                    for outcome in group {
+                        // the dashboard may have closed already
+                        let _ = results.send(outcome);
+                    }
+                },
+            );
+        });
+    }
+
+    fn finished(&mut self, outcome: PuzzleOutcome) {
+        let puzzle = (outcome.day, outcome.part);
+        self.running.remove(&puzzle);
+        if let Some(path) = &self.history {
+            // a history that can't be written shouldn't stop the dashboard
+            if history::record(path, std::slice::from_ref(&outcome)).is_ok()
+                && outcome.answer.is_ok()
+            {
+                self.recorded.insert(puzzle, outcome.elapsed);
+            }
+        }
+        self.results.insert(puzzle, outcome);
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        queue!(
+            out,
+            Clear(ClearType::All),
+            MoveTo(0, 0),
+            Print(format!("Advent of Code {}\r\n\r\n", self.year))
+        )?;
+        for week in 0..DAYS / WEEK {
+            for day in (week * WEEK + 1)..=(week * WEEK + WEEK) {
+                let (open, close) = if day == self.selected {
+                    ('[', ']')
+                } else {
+                    (' ', ' ')
+                };
+                queue!(out, Print(format!("{open}{day:>2} ")))?;
+                for part in 1..=2 {
+                    let (symbol, color) = self.part_state(day, part).symbol();
+                    queue!(out, SetForegroundColor(color), Print(symbol), ResetColor)?;
+                }
+                queue!(out, Print(format!("{close} ")))?;
+            }
+            queue!(out, Print("\r\n"))?;
+        }
+        queue!(out, Print(format!("\r\nDay {}\r\n", self.selected)))?;
+        for part in 1..=2 {
+            queue!(
+                out,
+                Print(format!("  {}\r\n", self.details(self.selected, part)))
+            )?;
+        }
+        queue!(
+            out,
+            Print(
+                "\r\n* solved  x wrong answer  ! failed  ~ running  o ready  . no input\r\n\
+                 arrows/hjkl move  enter run day  a run all  q quit\r\n"
+            )
+        )?;
+        out.flush()
+    }
+
+    /// One line describing a part's input, known answer, recorded time and latest result
+    fn details(&self, day: u8, part: u8) -> String {
+        let Some(test) = self.tests.get(&(day, part)) else {
+            return format!("Part {part}: not solved yet");
+        };
+        let mut details = vec![match test.input_size() {
+            Some(size) => format!("input {size} bytes"),
+            None => "no input".to_string(),
+        }];
+        if let Some(known) = self.answers.get(day, part) {
+            details.push(format!("known {known}"));
+        }
+        if let Some(recorded) = self.recorded.get(&(day, part)) {
+            details.push(format!("recorded {recorded:.2?}"));
+        }
+        match self.results.get(&(day, part)) {
+            Some(PuzzleOutcome {
+                answer: Ok(answer),
+                elapsed,
+                ..
+            }) => details.push(format!("answer {answer} in {elapsed:.2?}")),
+            Some(PuzzleOutcome {
+                answer: Err(failure),
+                ..
+            }) => details.push(format!("FAILED: {failure}")),
+            None => {}
+        }
+        format!("Part {part}: {}", details.join(", "))
+    }
+}
+
+/// Restores the terminal when dropped, even if the dashboard bails out with an error
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl TestRunner {
+    /// Shows every puzzle of the year in a calendar, running them from the keyboard. Successful
+    /// runs are added to `history` when given
+    pub fn tui(
+        year: u16,
+        answers: PathBuf,
+        history: Option<PathBuf>,
+        timeouts: Timeouts,
+    ) -> io::Result<bool> {
+        let mut dashboard = Dashboard {
+            year,
+            selected: 1,
+            tests: Self::sorted(year)
+                .into_iter()
+                .map(|test| ((test.day, test.part), test))
+                .collect(),
+            answers: Answers::load(answers)?,
+            recorded: match &history {
+                Some(path) => history::latest_durations(path, year)?,
+                None => BTreeMap::new(),
+            },
+            results: BTreeMap::new(),
+            running: BTreeSet::new(),
+            history,
+            timeouts,
+        };
+        let (sender, receiver): (_, Receiver<PuzzleOutcome>) = mpsc::channel();
+        let mut out = io::stdout();
+        let _terminal = RawTerminal::enter(&mut out)?;
+        loop {
+            while let Ok(outcome) = receiver.try_recv() {
+                dashboard.finished(outcome);
+            }
+            dashboard.draw(&mut out)?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match Action::from(key) {
+                Action::Move(offset) => dashboard.select(offset),
+                Action::RunSelected => {
+                    let day = dashboard.selected;
+                    let tests = dashboard
+                        .tests
+                        .range((day, 0)..=(day, u8::MAX))
+                        .map(|(_, test)| *test)
+                        .collect();
+                    dashboard.run(tests, &sender);
+                }
+                Action::RunAll => {
+                    let tests = dashboard.tests.values().copied().collect();
+                    dashboard.run(tests, &sender);
+                }
+                Action::Quit => return Ok(true),
+                Action::Nothing => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{Answers, PuzzleOutcome, Timeouts};
+
+    use super::{Action, Dashboard, PartState};
+
+    fn dashboard() -> Dashboard {
+        Dashboard {
+            year: 2023,
+            selected: 1,
+            tests: BTreeMap::new(),
+            answers: "1 1 142\n".parse::<Answers>().unwrap(),
+            recorded: BTreeMap::new(),
+            results: BTreeMap::new(),
+            running: BTreeSet::new(),
+            history: None,
+            timeouts: Timeouts::default(),
+        }
+    }
+
+    #[test]
+    fn navigation() {
+        let mut dashboard = dashboard();
+        dashboard.select(-1);
+        assert_eq!(dashboard.selected, 1);
+        dashboard.select(5);
+        dashboard.select(1);
+        assert_eq!(dashboard.selected, 7);
+        dashboard.select(25);
+        assert_eq!(dashboard.selected, 7);
+        assert_eq!(
+            Action::from(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)),
+            Action::Move(5)
+        );
+        assert_eq!(
+            Action::from(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Action::Quit
+        );
+    }
+
+    #[test]
+    fn states() {
+        let test = crate::TestRunner::sorted(2022)[0];
+        let mut dashboard = dashboard();
+        assert_eq!(dashboard.part_state(1, 1), PartState::Missing);
+        dashboard.tests.insert((1, 1), test);
+        // the test runner's input doesn't exist
+        assert_eq!(dashboard.part_state(1, 1), PartState::NoInput);
+        dashboard.running.insert((1, 1));
+        assert_eq!(dashboard.part_state(1, 1), PartState::Running);
+
+        dashboard.finished(PuzzleOutcome::test(1, 1, Ok("142")));
+        assert_eq!(dashboard.part_state(1, 1), PartState::Passed);
+        dashboard.finished(PuzzleOutcome::test(1, 1, Ok("141")));
+        assert_eq!(dashboard.part_state(1, 1), PartState::Wrong);
+        dashboard.finished(PuzzleOutcome::test(1, 1, Err("panicked")));
+        assert_eq!(dashboard.part_state(1, 1), PartState::Failed);
+        assert!(dashboard.details(1, 1).contains("FAILED: panicked"));
+        assert_eq!(dashboard.details(2, 1), "Part 1: not solved yet");
+    }
+}
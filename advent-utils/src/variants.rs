@@ -0,0 +1,110 @@
+use crate::{parser, pool, InputSource, PuzzleOutcome, TestRunner, Timeouts};
+
+/// What the unnamed solution of a puzzle is called when listed next to named variants
+pub(crate) const DEFAULT_VARIANT: &str = "default";
+
+impl PuzzleOutcome {
+    pub(crate) fn variant_name(&self) -> &'static str {
+        self.variant.unwrap_or(DEFAULT_VARIANT)
+    }
+}
+
+/// The answer every variant of a puzzle gave, `None` if any failed or they disagree
+fn agreed(outcomes: &[PuzzleOutcome]) -> Option<&str> {
+    let (first, rest) = outcomes.split_first()?;
+    let answer = first.answer.as_ref().ok()?;
+    rest.iter()
+        .all(|outcome| outcome.answer.as_ref() == Ok(answer))
+        .then_some(answer.as_str())
+}
+
+impl TestRunner {
+    /// Runs every variant of the selected puzzles and reports the ones whose answers disagree,
+    /// puzzles with a single solution are skipped. Returns false if any variant failed or
+    /// disagreed
+    pub fn cross_check(
+        year: u16,
+        day: Option<u8>,
+        part: Option<u8>,
+        jobs: usize,
+        timeouts: &Timeouts,
+    ) -> bool {
+        let tests = Self::variants(year)
+            .into_iter()
+            .filter(|test| day.is_none_or(|day| day == test.day))
+            .filter(|test| part.is_none_or(|part| part == test.part))
+            .collect::<Vec<_>>();
+        let mut outcomes = vec![];
+        pool::run_in_order(
+            &parser::groups(&tests),
+            jobs,
+            |group| Self::run_group(group, &InputSource::Default, timeouts),
+            |group| outcomes.extend(group),
+        );
+
+        let (mut checked, mut passed) = (0, true);
+        for puzzle in outcomes.chunk_by(|a, b| (a.day, a.part) == (b.day, b.part)) {
+            if puzzle.len() < 2 {
+                continue;
+            }
+            checked += 1;
+            let (day, part) = (puzzle[0].day, puzzle[0].part);
+            if let Some(answer) = agreed(puzzle) {
+                println!(
+                    "Day {day} Part {part}: {} variants agree on {answer}",
+                    puzzle.len()
+                );
+                continue;
+            }
+            passed = false;
+            println!("Day {day} Part {part}: DISAGREE");
+            for outcome in puzzle {
+                match &outcome.answer {
+                    Ok(answer) => println!(
+                        "  {}: {answer} ({:.2?})",
+                        outcome.variant_name(),
+                        outcome.elapsed
+                    ),
+                    Err(failure) => println!("  {}: FAILED: {failure}", outcome.variant_name()),
+                }
+            }
+        }
+        if checked == 0 {
+            println!("No puzzles with more than one variant");
+        }
+        passed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::PuzzleOutcome;
+
+    use super::agreed;
+
+    fn outcome(variant: Option<&'static str>, answer: Result<&str, &str>) -> PuzzleOutcome {
+        PuzzleOutcome {
+            variant,
+            ..PuzzleOutcome::test(5, 2, answer)
+        }
+    }
+
+    #[test]
+    fn agreement() {
+        assert_eq!(
+            agreed(&[outcome(None, Ok("46")), outcome(Some("brute"), Ok("46"))]),
+            Some("46")
+        );
+        assert_eq!(
+            agreed(&[outcome(None, Ok("46")), outcome(Some("brute"), Ok("45"))]),
+            None
+        );
+        assert_eq!(
+            agreed(&[
+                outcome(None, Ok("46")),
+                outcome(Some("brute"), Err("panicked"))
+            ]),
+            None
+        );
+    }
+}
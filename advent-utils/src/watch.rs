@@ -0,0 +1,133 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{InputSource, RunOptions, TestRunner};
+
+/// Separates examples in a day's examples file
+const EXAMPLE_SEPARATOR: &str = "---";
+
+/// Where a day's examples are kept, next to its input as `day_N.examples`
+fn examples_path(file: &str) -> PathBuf {
+    PathBuf::from(format!("{}.examples", file.trim_end_matches('/')))
+}
+
+/// Splits an examples file into its examples, which are separated by `---` lines
+fn parse_examples(text: &str) -> Vec<String> {
+    let mut examples = vec![String::new()];
+    for line in text.lines() {
+        if line.trim() == EXAMPLE_SEPARATOR {
+            examples.push(String::new());
+        } else {
+            let example = examples.last_mut().unwrap();
+            example.push_str(line);
+            example.push('\n');
+        }
+    }
+    examples.retain(|example| !example.trim().is_empty());
+    examples
+}
+
+/// Last modification time and size of a file, `None` if it doesn't exist
+fn file_state(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Describes an answer next to what it was on the previous run
+fn describe_change(previous: Option<&str>, answer: &str) -> String {
+    match previous {
+        None => answer.to_string(),
+        Some(previous) if previous == answer => format!("{answer} (unchanged)"),
+        Some(previous) => format!("{answer} (was {previous})"),
+    }
+}
+
+impl TestRunner {
+    /// Re-runs a puzzle against its examples and input every time one of them changes, only
+    /// returns if the puzzle can't be found
+    pub fn watch(day: u8, part: u8, options: &RunOptions, interval: Duration) -> bool {
+        let test = match Self::find(options.year, day, part, options.variant.as_deref()) {
+            Ok(test) => test,
+            Err(e) => {
+                eprintln!("{e}");
+                return false;
+            }
+        };
+        let input = options.input.path(test.file);
+        let examples = examples_path(test.file);
+        let mut seen = None;
+        let mut previous = HashMap::new();
+        loop {
+            let state = (input.as_deref().and_then(file_state), file_state(&examples));
+            if seen.as_ref() != Some(&state) {
+                seen = Some(state);
+                // clear the screen and move the cursor to the top
+                print!("\x1B[2J\x1B[H");
+                println!("Day: {day} Part: {part}");
+                let mut runs = fs::read_to_string(&examples)
+                    .map(|text| parse_examples(&text))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, example)| {
+                        (format!("Example {}", i + 1), InputSource::Inline(example))
+                    })
+                    .collect::<Vec<_>>();
+                runs.push(("Input".to_string(), options.input.clone()));
+                for (label, source) in runs {
+                    let outcome =
+                        test.run_with_timeout(&source, options.timeouts.for_puzzle(day, part));
+                    let answer = match &outcome.answer {
+                        Ok(answer) => answer.clone(),
+                        Err(failure) => format!("FAILED: {failure}"),
+                    };
+                    println!(
+                        "{label}: {} in {:.2?}",
+                        describe_change(previous.get(&label).map(String::as_str), &answer),
+                        outcome.elapsed
+                    );
+                    previous.insert(label, answer);
+                }
+                println!(
+                    "Watching for changes, examples are read from {}",
+                    examples.display()
+                );
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{describe_change, examples_path, parse_examples};
+
+    #[test]
+    fn examples() {
+        assert_eq!(
+            parse_examples("1abc2\npqr3stu8vwx\n---\ntwo1nine\n---\n\n"),
+            vec!["1abc2\npqr3stu8vwx\n", "two1nine\n"]
+        );
+        assert!(parse_examples("").is_empty());
+        assert_eq!(
+            examples_path("./test_data/day_1").to_str(),
+            Some("./test_data/day_1.examples")
+        );
+        assert_eq!(
+            examples_path("./test_data/day_5/").to_str(),
+            Some("./test_data/day_5.examples")
+        );
+    }
+
+    #[test]
+    fn changes() {
+        assert_eq!(describe_change(None, "142"), "142");
+        assert_eq!(describe_change(Some("142"), "142"), "142 (unchanged)");
+        assert_eq!(describe_change(Some("141"), "142"), "142 (was 141)");
+    }
+}
@@ -1,17 +1,48 @@
-use darling::{ast::NestedMeta, Error, FromMeta};
+use darling::{ast::NestedMeta, util::SpannedValue, Error, FromMeta};
 use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, ItemFn, Visibility};
+use syn::{
+    parse_macro_input, spanned::Spanned, FnArg, GenericArgument, GenericParam, ItemFn, Meta, Path,
+    PathArguments, ReturnType, Type, TypeParamBound, Visibility,
+};
 
 mod kw {
     syn::custom_keyword!(day);
     syn::custom_keyword!(part);
 }
 
+/// Year of puzzles without a `year`, matches `advent_utils::DEFAULT_YEAR`
+const DEFAULT_YEAR: u16 = 2023;
+
+/// Name the unnamed solution is listed under, matches `advent_utils`' `DEFAULT_VARIANT`
+const DEFAULT_VARIANT: &str = "default";
+
+/// Profile embedded for days with a directory of inputs, matches `advent_utils::DEFAULT_PROFILE`
+const DEFAULT_PROFILE: &str = "default";
+
 #[derive(Debug, FromMeta)]
 struct DayArgs {
-    day: u8,
-    part: u8,
+    year: Option<u16>,
+    day: SpannedValue<u8>,
+    part: SpannedValue<u8>,
+    /// Name for an alternative solution to the same puzzle
+    variant: Option<SpannedValue<String>>,
+    /// Sample input checked by a generated test, followed by its `expected` answer
+    #[darling(multiple)]
+    example: Vec<String>,
+    /// Like `example`, read from a file relative to the crate root
+    #[darling(multiple)]
+    example_file: Vec<String>,
+    #[darling(multiple)]
+    expected: Vec<String>,
+}
+
+/// Sample input for a generated test
+#[derive(Debug, PartialEq)]
+enum Example {
+    Inline(String),
+    File(String),
 }
 
 #[proc_macro_attribute]
@@ -26,34 +57,746 @@ pub fn advent_of_code(
                 return TokenStream::from(Error::from(e).write_errors());
             }
         };
-    let DayArgs { day, part } = match DayArgs::from_list(&attr_args) {
+    let DayArgs {
+        year,
+        day,
+        part,
+        variant,
+        example,
+        example_file,
+        expected,
+    } = match DayArgs::from_list(&attr_args) {
         Ok(v) => v,
         Err(e) => return TokenStream::from(e.write_errors()),
     };
-    let struct_name = format_ident!("Day{day}Part{part}");
-    let file_path = format!("./test_data/day_{day}");
+    let year = year.unwrap_or(DEFAULT_YEAR);
     // println!("item: \"{}\"", item.to_string());
     let mut function = parse_macro_input!(item as ItemFn);
+    let input = match (
+        validate(&day, Some(&part), &function),
+        validate_variant(variant.as_ref()),
+    ) {
+        (Ok(input), Ok(())) => input,
+        (Err(mut e), Err(variant)) => {
+            e.combine(variant);
+            return e.to_compile_error().into();
+        }
+        (Err(e), _) | (_, Err(e)) => return e.to_compile_error().into(),
+    };
+    let examples = match examples(&attr_args, example, example_file, expected) {
+        Ok(examples) => examples,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let (day, part) = (*day, *part);
+    // function names are unique within the module, unlike variant names once they're made into
+    // an identifier
+    let struct_name = format_ident!("Year{year}Day{day}Part{part}_{}", function.sig.ident);
+    let variant = match variant.as_deref() {
+        Some(variant) => quote! { Some(#variant) },
+        None => quote! { None },
+    };
+    // the default year keeps the original layout so existing inputs don't have to move
+    let file_path = if year == DEFAULT_YEAR {
+        format!("./test_data/day_{day}")
+    } else {
+        format!("./test_data/{year}/day_{day}")
+    };
+    // decided here rather than with a `cfg` in the generated code, so crates using the macro
+    // don't need an `embed` feature of their own
+    let embedded_input = if cfg!(feature = "embed") {
+        let embedded = embedded_file(
+            &std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default(),
+            &file_path,
+        );
+        quote! {
+            const INPUT : Option<&'static str> = Some(include_str!(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/", #embedded)
+            ));
+        }
+    } else {
+        quote! {}
+    };
     function.vis = Visibility::Inherited;
     let function_name = &function.sig.ident;
+    let arguments = input.arguments();
+    let solve = if returns_result(&function.sig.output) {
+        quote! {
+            #function_name(#arguments)
+                .map(|answer| answer.to_string())
+                .map_err(Into::into)
+        }
+    } else {
+        quote! {
+            Ok(#function_name(#arguments).to_string())
+        }
+    };
+    let (run, parsed_part, runner) = if input == Input::Parsed {
+        // errors about a missing parser point at the parameter expecting its output
+        let span = function
+            .sig
+            .inputs
+            .first()
+            .map_or_else(Span::call_site, |input| input.span());
+        let parser = format_ident!("Year{year}Day{day}Parser", span = span);
+        (
+            quote! {
+                let parsed = <#parser as DayParser>::parse(lines)?;
+                <Self as ParsedDayPart>::solve(&parsed)
+            },
+            quote! {
+                impl ParsedDayPart for #struct_name {
+                    type Parser = #parser;
+
+                    fn solve(parsed: &<Self::Parser as DayParser>::Output) -> PuzzleResult {
+                        #solve
+                    }
+                }
+            },
+            quote! { TestRunner::parsed::<#struct_name>() },
+        )
+    } else {
+        (
+            solve,
+            quote! {},
+            quote! { TestRunner::new::<#struct_name>() },
+        )
+    };
+    let tests = examples.iter().enumerate().map(|(i, (example, expected))| {
+        let test_name = format_ident!("{function_name}_example_{}", i + 1);
+        let input = match example {
+            Example::Inline(input) => quote! { #input.to_string() },
+            Example::File(path) => quote! {{
+                let path = concat!(env!("CARGO_MANIFEST_DIR"), "/", #path);
+                ::std::fs::read_to_string(path)
+                    .unwrap_or_else(|e| panic!("unable to read {path}: {e}"))
+            }},
+        };
+        quote! {
+            #[cfg(test)]
+            #[test]
+            fn #test_name() {
+                let input = #input;
+                let answer = <#struct_name as DayPart>::run(input.lines().map(str::to_string));
+                assert_eq!(answer.map_err(|e| e.to_string()), Ok(#expected.to_string()));
+            }
+        }
+    });
     let tokens = quote! {
         #function
 
+        #(#tests)*
+
+        #[allow(non_camel_case_types)]
         pub struct #struct_name;
 
         impl DayPart for #struct_name {
             const FILE : &'static str = #file_path;
+            #embedded_input
+            const YEAR : u16 = #year;
             const DAY : u8 = #day;
             const PART : u8 = #part;
+            const VARIANT : Option<&'static str> = #variant;
+            const SOURCE : &'static str = concat!(module_path!(), "::", stringify!(#function_name));
 
-            fn run(lines: impl Iterator<Item = String>) -> String {
-                #function_name(lines)
+            fn run(lines: impl Iterator<Item = String>) -> PuzzleResult {
+                #run
             }
         }
 
+        #parsed_part
+
         inventory::submit! {
-            TestRunner::new::<#struct_name>()
+            #runner
+        }
+    };
+    tokens.into()
+}
+
+#[derive(Debug, FromMeta)]
+struct ParserArgs {
+    year: Option<u16>,
+    day: SpannedValue<u8>,
+}
+
+/// Marks a function that parses a day's input for both of its parts, which take a reference to
+/// what it returns instead of the input. The input is parsed once when both parts are run
+///
+/// The parser has to be in scope of the parts, and takes its input like a solution does. It may
+/// return a `Result` to fail the parts on bad input
+#[proc_macro_attribute]
+pub fn advent_parser(
+    args: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let attr_args = match NestedMeta::parse_meta_list(args.into()) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(Error::from(e).write_errors()),
+    };
+    let ParserArgs { year, day } = match ParserArgs::from_list(&attr_args) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+    let year = year.unwrap_or(DEFAULT_YEAR);
+    let mut function = parse_macro_input!(item as ItemFn);
+    let input = match validate(&day, None, &function) {
+        Ok(input) => input,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let parser = format_ident!("Year{year}Day{}Parser", *day);
+    function.vis = Visibility::Inherited;
+    let function_name = &function.sig.ident;
+    let arguments = input.arguments();
+    let (output, parse) = match &function.sig.output {
+        ReturnType::Type(_, ty) if returns_result(&function.sig.output) => {
+            let Some(output) = result_ok_type(ty) else {
+                return syn::Error::new_spanned(ty, "expected a `Result<T, E>` to take T from")
+                    .to_compile_error()
+                    .into();
+            };
+            (
+                quote! { #output },
+                quote! { #function_name(#arguments).map_err(Into::into) },
+            )
+        }
+        ReturnType::Type(_, ty) => (quote! { #ty }, quote! { Ok(#function_name(#arguments)) }),
+        // validate rejects functions without a return type
+        ReturnType::Default => (quote! { () }, quote! { Ok(#function_name(#arguments)) }),
+    };
+    let tokens = quote! {
+        #function
+
+        pub struct #parser;
+
+        impl DayParser for #parser {
+            type Output = #output;
+
+            fn parse(lines: impl Iterator<Item = String>) -> Result<Self::Output, PuzzleError> {
+                #parse
+            }
         }
     };
     tokens.into()
 }
+
+/// The `T` of a `Result<T, E>`
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(arguments) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// How a solution takes the puzzle input
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Input {
+    /// `impl Iterator<Item = String>`
+    Lines,
+    /// `Vec<String>`
+    Vec,
+    /// `&str` of the whole input
+    Str,
+    /// `&[u8]` of the whole input
+    Bytes,
+    /// `impl BufRead`
+    Reader,
+    /// A reference to the output of the day's `#[advent_parser]`
+    Parsed,
+}
+
+impl Input {
+    /// What the function is called with, from the `lines` iterator or the day's `parsed` input
+    fn arguments(self) -> TokenStream2 {
+        // whole input solutions get the lines back with their line endings
+        let whole_input = quote! { lines.map(|line| line + "\n").collect::<String>() };
+        match self {
+            Input::Lines => quote! { lines },
+            Input::Vec => quote! { lines.collect::<Vec<String>>() },
+            Input::Str => quote! { &#whole_input },
+            Input::Bytes => quote! { #whole_input.as_bytes() },
+            Input::Reader => quote! { ::std::io::Cursor::new(#whole_input) },
+            Input::Parsed => quote! { parsed },
+        }
+    }
+
+    fn from_type(ty: &Type) -> Option<Self> {
+        match ty {
+            Type::ImplTrait(impl_trait) => impl_trait.bounds.iter().find_map(|bound| match bound {
+                TypeParamBound::Trait(bound) if ends_with(&bound.path, "Iterator") => {
+                    Some(Input::Lines)
+                }
+                TypeParamBound::Trait(bound) if ends_with(&bound.path, "BufRead") => {
+                    Some(Input::Reader)
+                }
+                _ => None,
+            }),
+            Type::Reference(reference) if reference.mutability.is_none() => {
+                match reference.elem.as_ref() {
+                    Type::Path(path) if ends_with(&path.path, "str") => Some(Input::Str),
+                    Type::Slice(slice) => match slice.elem.as_ref() {
+                        Type::Path(path) if ends_with(&path.path, "u8") => Some(Input::Bytes),
+                        _ => None,
+                    },
+                    _ => Some(Input::Parsed),
+                }
+            }
+            Type::Path(path) => {
+                let segment = path.path.segments.last()?;
+                let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                    return None;
+                };
+                let strings = matches!(
+                    arguments.args.first(),
+                    Some(GenericArgument::Type(Type::Path(item))) if ends_with(&item.path, "String")
+                );
+                (segment.ident == "Vec" && strings).then_some(Input::Vec)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn ends_with(path: &Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+/// Checks the attribute's day and part are a real puzzle and that the function can be called
+/// with the puzzle input, so mistakes point at the attribute or function instead of the code
+/// generated from them. Returns how the function takes its input
+///
+/// Parsers are validated without a part, and can't take parsed input themselves
+fn validate(
+    day: &SpannedValue<u8>,
+    part: Option<&SpannedValue<u8>>,
+    function: &ItemFn,
+) -> syn::Result<Input> {
+    let mut errors = vec![];
+    if !(1..=25).contains(&**day) {
+        errors.push(syn::Error::new(
+            day.span(),
+            format!("day must be between 1 and 25, found {}", **day),
+        ));
+    }
+    if let Some(part) = part.filter(|part| !(1..=2).contains(&***part)) {
+        errors.push(syn::Error::new(
+            part.span(),
+            format!("part must be 1 or 2, found {}", **part),
+        ));
+    }
+
+    let signature = &function.sig;
+    if let Some(asyncness) = signature.asyncness {
+        errors.push(syn::Error::new(
+            asyncness.span(),
+            "puzzle solutions can't be async",
+        ));
+    }
+    // lifetimes are fine, the runner can't pick types or constants for the solution though
+    for param in &signature.generics.params {
+        if let GenericParam::Type(_) | GenericParam::Const(_) = param {
+            errors.push(syn::Error::new_spanned(
+                param,
+                "puzzle solutions can't have type or const parameters, take \
+                 `impl Iterator<Item = String>` instead",
+            ));
+        }
+    }
+    let mut input = None;
+    let mut inputs = signature.inputs.iter();
+    match (inputs.next(), inputs.next()) {
+        (None, _) => errors.push(syn::Error::new(
+            signature.paren_token.span.join(),
+            "puzzle solutions take the puzzle input as their only parameter, \
+             like `input: &str` or `lines: impl Iterator<Item = String>`",
+        )),
+        (Some(FnArg::Receiver(receiver)), _) => errors.push(syn::Error::new_spanned(
+            receiver,
+            "puzzle solutions must be free functions, not methods",
+        )),
+        (Some(FnArg::Typed(typed)), extra) => {
+            input = Input::from_type(&typed.ty);
+            match input {
+                None => errors.push(syn::Error::new_spanned(
+                    &typed.ty,
+                    "expected the input as `impl Iterator<Item = String>`, `Vec<String>`, \
+                     `&str`, `&[u8]`, `impl BufRead` or a reference to `#[advent_parser]` output",
+                )),
+                Some(Input::Parsed) if part.is_none() => errors.push(syn::Error::new_spanned(
+                    &typed.ty,
+                    "expected the input as `impl Iterator<Item = String>`, `Vec<String>`, \
+                     `&str`, `&[u8]` or `impl BufRead`",
+                )),
+                Some(_) => {}
+            }
+            if let Some(extra) = extra {
+                errors.push(syn::Error::new_spanned(
+                    extra,
+                    "puzzle solutions take the puzzle input as their only parameter",
+                ));
+            }
+        }
+    }
+    if let ReturnType::Default = signature.output {
+        errors.push(syn::Error::new(
+            signature.ident.span(),
+            "puzzle solutions must return their answer",
+        ));
+    }
+
+    match combine(errors) {
+        Some(errors) => Err(errors),
+        // a missing or unsupported input is always reported as an error
+        None => Ok(input.unwrap_or(Input::Lines)),
+    }
+}
+
+/// Pairs each `example` and `example_file` with the `expected` answer written after it
+fn examples(
+    args: &[NestedMeta],
+    inline: Vec<String>,
+    files: Vec<String>,
+    expected: Vec<String>,
+) -> syn::Result<Vec<(Example, String)>> {
+    // darling collects each argument separately, so their order comes from the arguments
+    let (mut inline, mut files, mut expected) =
+        (inline.into_iter(), files.into_iter(), expected.into_iter());
+    let mut examples = vec![];
+    let mut errors = vec![];
+    let mut pending = None;
+    for arg in args {
+        let NestedMeta::Meta(meta) = arg else {
+            continue;
+        };
+        let name = meta.path().get_ident().map(ToString::to_string);
+        let example = match name.as_deref() {
+            Some("example") => inline.next().map(|input| Example::Inline(dedent(&input))),
+            Some("example_file") => files.next().map(Example::File),
+            Some("expected") => {
+                match (pending.take(), expected.next()) {
+                    (Some((example, _)), Some(answer)) => examples.push((example, answer)),
+                    _ => errors.push(syn::Error::new_spanned(
+                        meta,
+                        "`expected` must follow an `example` or `example_file`",
+                    )),
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let Some(example) = example else {
+            continue;
+        };
+        if let Some((_, unanswered)) = pending.replace((example, meta)) {
+            errors.push(unanswered_error(unanswered));
+        }
+    }
+    if let Some((_, unanswered)) = pending {
+        errors.push(unanswered_error(unanswered));
+    }
+    match combine(errors) {
+        Some(errors) => Err(errors),
+        None => Ok(examples),
+    }
+}
+
+fn unanswered_error(example: &Meta) -> syn::Error {
+    syn::Error::new_spanned(example, "example has no `expected` answer after it")
+}
+
+/// Merges errors so they're all reported at once
+fn combine(errors: Vec<syn::Error>) -> Option<syn::Error> {
+    errors.into_iter().reduce(|mut combined, error| {
+        combined.combine(error);
+        combined
+    })
+}
+
+/// Removes the indentation shared by the example's lines, so examples can be written as an
+/// indented string literal like the ones in the day modules' tests
+///
+/// The first line is left alone as it follows the opening quote, unless it's empty in which
+/// case it's dropped and the lines after it are all dedented. Likewise a last line of only
+/// whitespace is the indentation before the closing quote, and is dropped
+fn dedent(text: &str) -> String {
+    let (first, rest) = match text.strip_prefix('\n') {
+        Some(rest) => (None, rest),
+        None => match text.split_once('\n') {
+            Some((first, rest)) => (Some(first), rest),
+            None => (Some(text), ""),
+        },
+    };
+    let rest = match rest.rsplit_once('\n') {
+        Some((rest, last)) if last.trim().is_empty() => rest,
+        _ => rest,
+    };
+    let indent = rest
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    first
+        .into_iter()
+        .chain(
+            rest.lines()
+                .map(|line| line.get(indent..).unwrap_or(line.trim_start())),
+        )
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+/// Variant names have to be told apart from the unnamed solution when listing and picking them
+fn validate_variant(variant: Option<&SpannedValue<String>>) -> syn::Result<()> {
+    match variant {
+        Some(variant) if variant.trim().is_empty() => Err(syn::Error::new(
+            variant.span(),
+            "variant names can't be empty, leave out `variant` for the usual solution",
+        )),
+        Some(variant) if variant.as_str() == DEFAULT_VARIANT => Err(syn::Error::new(
+            variant.span(),
+            "`default` is the name of the usual solution, leave out `variant` instead",
+        )),
+        // variant names end up on the command line and in the history, space separated
+        Some(variant) if variant.contains(char::is_whitespace) => Err(syn::Error::new(
+            variant.span(),
+            "variant names can't contain whitespace, try `-` or `_` instead",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The file the `embed` feature bakes into the binary, relative to the crate root. Days with a
+/// directory of profiles embed the default one
+fn embedded_file(manifest_dir: &str, file_path: &str) -> String {
+    let file = file_path.trim_start_matches("./");
+    if std::path::Path::new(manifest_dir).join(file).is_dir() {
+        format!("{file}/{DEFAULT_PROFILE}.txt")
+    } else {
+        file.to_string()
+    }
+}
+
+/// Solutions returning a `Result` have their error passed on to the runner, anything else is
+/// treated as an answer that can't fail
+fn returns_result(output: &ReturnType) -> bool {
+    match output {
+        ReturnType::Type(_, ty) => match ty.as_ref() {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Result"),
+            _ => false,
+        },
+        ReturnType::Default => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use darling::{ast::NestedMeta, FromMeta};
+    use syn::{parse_quote, ItemFn, Meta};
+
+    use super::{
+        dedent, embedded_file, validate, validate_variant, DayArgs, Example, Input, ParserArgs,
+    };
+
+    fn check(attribute: Meta, function: ItemFn) -> Result<Input, Vec<String>> {
+        let args = DayArgs::from_meta(&attribute).unwrap();
+        validate(&args.day, Some(&args.part), &function)
+            .map_err(|e| e.into_iter().map(|e| e.to_string()).collect())
+    }
+
+    #[test]
+    fn validation() {
+        let solution: ItemFn = parse_quote! {
+            fn part_1(lines: impl Iterator<Item = String>) -> String { String::new() }
+        };
+        assert_eq!(
+            check(
+                parse_quote! { advent_of_code(day = 25, part = 2) },
+                solution.clone()
+            ),
+            Ok(Input::Lines)
+        );
+        assert_eq!(
+            check(
+                parse_quote! { advent_of_code(day = 1, part = 1) },
+                parse_quote! { fn part_1<'a>(input: &'a str) -> usize { input.len() } }
+            ),
+            Ok(Input::Str)
+        );
+        assert_eq!(
+            check(
+                parse_quote! { advent_of_code(day = 1, part = 1) },
+                parse_quote! { fn part_1<T>(input: &str) -> usize { input.len() } }
+            ),
+            Err(vec![
+                "puzzle solutions can't have type or const parameters, take \
+                 `impl Iterator<Item = String>` instead"
+                    .to_string()
+            ])
+        );
+        assert_eq!(
+            check(
+                parse_quote! { advent_of_code(day = 26, part = 3) },
+                solution
+            ),
+            Err(vec![
+                "day must be between 1 and 25, found 26".to_string(),
+                "part must be 1 or 2, found 3".to_string(),
+            ])
+        );
+        assert_eq!(
+            check(
+                parse_quote! { advent_of_code(day = 1, part = 1) },
+                parse_quote! { async fn part_1(lines: Vec<&str>, extra: u8) {} }
+            ),
+            Err(vec![
+                "puzzle solutions can't be async".to_string(),
+                "expected the input as `impl Iterator<Item = String>`, `Vec<String>`, `&str`, \
+                 `&[u8]`, `impl BufRead` or a reference to `#[advent_parser]` output"
+                    .to_string(),
+                "puzzle solutions take the puzzle input as their only parameter".to_string(),
+                "puzzle solutions must return their answer".to_string(),
+            ])
+        );
+        // parsers take the puzzle input, not another parser's output
+        let parser: ItemFn = parse_quote! { fn parse(network: &Network) -> Network { todo!() } };
+        let args = ParserArgs::from_meta(&parse_quote! { advent_parser(day = 8) }).unwrap();
+        assert_eq!(
+            validate(&args.day, None, &parser).map_err(|e| e.to_string()),
+            Err(
+                "expected the input as `impl Iterator<Item = String>`, `Vec<String>`, `&str`, \
+                 `&[u8]` or `impl BufRead`"
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn variants() {
+        let variant = |attribute: Meta| {
+            let args = DayArgs::from_meta(&attribute).unwrap();
+            validate_variant(args.variant.as_ref()).map_err(|e| e.to_string())
+        };
+        assert_eq!(
+            variant(parse_quote! { advent_of_code(day = 5, part = 2, variant = "brute") }),
+            Ok(())
+        );
+        assert_eq!(
+            variant(parse_quote! { advent_of_code(day = 5, part = 2) }),
+            Ok(())
+        );
+        assert!(variant(parse_quote! { advent_of_code(day = 5, part = 2, variant = "") }).is_err());
+        assert!(
+            variant(parse_quote! { advent_of_code(day = 5, part = 2, variant = "default") })
+                .is_err()
+        );
+        assert!(variant(
+            parse_quote! { advent_of_code(day = 5, part = 2, variant = "brute force") }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn inputs() {
+        let inputs = [
+            (
+                parse_quote!(impl Iterator<Item = String>),
+                Some(Input::Lines),
+            ),
+            (parse_quote!(Vec<String>), Some(Input::Vec)),
+            (parse_quote!(&str), Some(Input::Str)),
+            (parse_quote!(&'a [u8]), Some(Input::Bytes)),
+            (parse_quote!(impl std::io::BufRead), Some(Input::Reader)),
+            (parse_quote!(&Network), Some(Input::Parsed)),
+            (parse_quote!(&mut str), None),
+            (parse_quote!(Vec<u8>), None),
+            (parse_quote!(String), None),
+        ];
+        for (ty, input) in inputs {
+            assert_eq!(Input::from_type(&ty), input);
+        }
+    }
+    #[test]
+    fn examples() {
+        let args = NestedMeta::parse_meta_list(parse_quote! {
+            day = 1,
+            part = 1,
+            example = "1abc2
+            pqr3stu8vwx",
+            expected = "142",
+            example_file = "test_data/day_1.example",
+            expected = "77"
+        })
+        .unwrap();
+        let DayArgs {
+            example,
+            example_file,
+            expected,
+            ..
+        } = DayArgs::from_list(&args).unwrap();
+        assert_eq!(
+            super::examples(&args, example, example_file, expected).unwrap(),
+            vec![
+                (
+                    Example::Inline("1abc2\npqr3stu8vwx\n".to_string()),
+                    "142".to_string()
+                ),
+                (
+                    Example::File("test_data/day_1.example".to_string()),
+                    "77".to_string()
+                ),
+            ]
+        );
+
+        let args = NestedMeta::parse_meta_list(
+            parse_quote! { day = 1, part = 1, expected = "1", example = "a" },
+        )
+        .unwrap();
+        let DayArgs {
+            example,
+            example_file,
+            expected,
+            ..
+        } = DayArgs::from_list(&args).unwrap();
+        let errors = super::examples(&args, example, example_file, expected)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "`expected` must follow an `example` or `example_file`",
+                "example has no `expected` answer after it",
+            ]
+        );
+    }
+
+    #[test]
+    fn dedenting() {
+        assert_eq!(
+            dedent("two1nine\n        eightwothree\n          7pqr"),
+            "two1nine\neightwothree\n  7pqr\n"
+        );
+        assert_eq!(dedent("\n    a\n\n      b\n    "), "a\n\n  b\n");
+        assert_eq!(dedent("\n    1\n    2\n    "), "1\n2\n");
+        assert_eq!(dedent("abc"), "abc\n");
+    }
+
+    #[test]
+    fn embedding() {
+        // this crate's own files stand in for a day's input file and directory of profiles
+        let manifest_dir = env!("CARGO_MANIFEST_DIR");
+        assert_eq!(embedded_file(manifest_dir, "./Cargo.toml"), "Cargo.toml");
+        assert_eq!(embedded_file(manifest_dir, "./src"), "src/default.txt");
+    }
+}
@@ -0,0 +1,76 @@
+//! Finds every `src/day*.rs` and declares it as a module, so a new day can't be forgotten
+//!
+//! Days from other years live in `src/year<year>/day*.rs` and end up in a `year<year>` module
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut modules = day_modules(&src);
+    let mut years = fs::read_dir(&src)
+        .expect("unable to read src")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?.to_string();
+            let year = name.strip_prefix("year")?;
+            (path.is_dir() && year.parse::<u16>().is_ok()).then_some((name, path))
+        })
+        .collect::<Vec<_>>();
+    years.sort();
+    for (name, path) in years {
+        modules.push_str(&format!("pub mod {name} {{\n{}}}\n", day_modules(&path)));
+    }
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(out, modules).expect("unable to write days.rs");
+}
+
+/// Declares every `day*.rs` in `dir`, warning about files that don't register a puzzle
+fn day_modules(dir: &Path) -> String {
+    let mut days = fs::read_dir(dir)
+        .expect("unable to read day modules")
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            (name.starts_with("day") && path.extension()? == "rs").then_some((name, path))
+        })
+        .collect::<Vec<(String, PathBuf)>>();
+    days.sort_by(|(a, _), (b, _)| day_order(a).cmp(&day_order(b)));
+
+    let mut modules = String::new();
+    for (name, path) in days {
+        let file = path.strip_prefix(dir.parent().unwrap()).unwrap_or(&path);
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            println!(
+                "cargo:warning={} is not a valid module name, skipping it",
+                file.display()
+            );
+            continue;
+        }
+        let source = fs::read_to_string(&path).expect("unable to read day module");
+        if !source.contains("#[advent_of_code") {
+            println!(
+                "cargo:warning={} defines no #[advent_of_code] function",
+                file.display()
+            );
+        }
+        modules.push_str(&format!(
+            "#[path = {:?}]\npub mod {name};\n",
+            path.display()
+        ));
+    }
+    modules
+}
+
+/// Orders `day10` after `day9` and `day7_part2` after `day7`
+fn day_order(name: &str) -> (u32, &str) {
+    let name = name.trim_start_matches("day");
+    let digits = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+    (name[..digits].parse().unwrap_or(u32::MAX), &name[digits..])
+}
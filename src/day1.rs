@@ -62,7 +62,15 @@ fn find_digit(input: &str) -> u32 {
     }
 }
 
-#[advent_of_code(day = 1, part = 1)]
+#[advent_of_code(
+    day = 1,
+    part = 1,
+    example = "1abc2
+    pqr3stu8vwx
+    a1b2c3d4e5f
+    treb7uchet",
+    expected = "142"
+)]
 pub fn day_1_part_1(lines: impl Iterator<Item = String>) -> String {
     let value = lines
         .map(|line| find_calibration_value_simple(line.as_str()))
@@ -70,7 +78,18 @@ pub fn day_1_part_1(lines: impl Iterator<Item = String>) -> String {
     value.to_string()
 }
 
-#[advent_of_code(day = 1, part = 2)]
+#[advent_of_code(
+    day = 1,
+    part = 2,
+    example = "two1nine
+    eightwothree
+    abcone2threexyz
+    xtwone3four
+    4nineeightseven2
+    zoneight234
+    7pqrstsixteen",
+    expected = "281"
+)]
 fn day_1_part_2(lines: impl Iterator<Item = String>) -> String {
     let value = lines
         .map(|line| find_calibration_value_text(line.as_str()))
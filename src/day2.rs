@@ -4,23 +4,21 @@ use advent::advent_of_code;
 use advent_utils::*;
 
 #[advent_of_code(day = 2, part = 1)]
-pub fn day_2_part_1(lines: impl Iterator<Item = String>) -> String {
+pub fn day_2_part_1(lines: impl Iterator<Item = String>) -> u32 {
     let mut possibilities = HashMap::new();
     possibilities.insert(Color::Red, 12);
     possibilities.insert(Color::Blue, 14);
     possibilities.insert(Color::Green, 13);
     let day_2_data = lines.map(|game| Game::from_line(game.as_str()));
-    let game_id_sum = possible_games(day_2_data, &possibilities)
+    possible_games(day_2_data, &possibilities)
         .map(|game| game.game_number)
-        .sum::<u32>();
-    game_id_sum.to_string()
+        .sum()
 }
 
 #[advent_of_code(day = 2, part = 2)]
-pub fn day_2_part_2(lines: impl Iterator<Item = String>) -> String {
+pub fn day_2_part_2(lines: impl Iterator<Item = String>) -> u32 {
     let day_2_data = lines.map(|game| Game::from_line(game.as_str()));
-    let sum = day_2_data.map(min_round).map(power).sum::<u32>();
-    sum.to_string()
+    day_2_data.map(min_round).map(power).sum()
 }
 
 fn power(round: Round) -> u32 {
@@ -126,6 +124,7 @@ fn possible_games<'a>(
 }
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod test {
     use std::collections::HashMap;
 
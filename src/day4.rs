@@ -24,7 +24,7 @@ pub fn day_4_part_2(lines: impl Iterator<Item = String>) -> String {
     sum.to_string()
 }
 
-fn process_pile(cards: &Vec<Card>) -> u32 {
+fn process_pile(cards: &[Card]) -> u32 {
     let mut counts = vec![1; cards.len()];
     for (i, card) in cards.iter().enumerate() {
         let matches = card.num_matching();
@@ -77,6 +77,7 @@ impl Card {
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_closure)]
 mod test {
     use crate::day4::process_pile;
 
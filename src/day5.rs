@@ -1,16 +1,29 @@
+use std::ops::Range;
+
 use advent::advent_of_code;
 use advent_utils::*;
 
 #[advent_of_code(day = 5, part = 1)]
 fn part_1(lines: impl Iterator<Item = String>) -> String {
     let data = Data::read_data(lines);
+    span!("map_seeds");
     let smallest = data.map_seeds().min().unwrap();
     smallest.to_string()
 }
 
 #[advent_of_code(day = 5, part = 2)]
 fn part_2(lines: impl Iterator<Item = String>) -> String {
-    let smallest = Data::read_data(lines).map_seed_ranges().min().unwrap();
+    let data = Data::read_data(lines);
+    span!("min_range_location");
+    let smallest = data.min_range_location().unwrap();
+    smallest.to_string()
+}
+
+#[advent_of_code(day = 5, part = 2, variant = "brute")]
+fn part_2_brute(lines: impl Iterator<Item = String>) -> String {
+    let data = Data::read_data(lines);
+    span!("map_seed_ranges");
+    let smallest = data.map_seed_ranges().min().unwrap();
     smallest.to_string()
 }
 
@@ -32,7 +45,8 @@ impl MappedRange {
         let range = self.start..(self.start + self.length);
         range
             .contains(&value)
-            .then_some((value - self.start) + self.dest_value)
+            // lazily, a value below the range would overflow the subtraction
+            .then(|| (value - self.start) + self.dest_value)
     }
 }
 
@@ -70,6 +84,35 @@ impl Map {
         )
     }
 
+    /// Maps a range of values, splitting it wherever it crosses the edge of a mapped range
+    fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut unmapped = vec![range];
+        let mut mapped = vec![];
+        for map in &self.maps {
+            let map_end = map.start + map.length;
+            let mut remaining = vec![];
+            for range in unmapped {
+                let start = range.start.max(map.start);
+                let end = range.end.min(map_end);
+                if start >= end {
+                    remaining.push(range);
+                    continue;
+                }
+                let dest = start - map.start + map.dest_value;
+                mapped.push(dest..dest + (end - start));
+                if range.start < start {
+                    remaining.push(range.start..start);
+                }
+                if end < range.end {
+                    remaining.push(end..range.end);
+                }
+            }
+            unmapped = remaining;
+        }
+        mapped.extend(unmapped);
+        mapped
+    }
+
     fn map_value(&self, value: u64) -> u64 {
         let outgoing = self
             .maps
@@ -87,6 +130,7 @@ struct Data {
 }
 impl Data {
     fn read_data(mut data: impl Iterator<Item = String>) -> Data {
+        span!("read_data");
         let seeds = data.next().unwrap();
         let (header, seeds) = seeds.split_once(':').unwrap();
         assert_eq!(header, "seeds");
@@ -112,6 +156,28 @@ impl Data {
         self.seeds.iter().copied().map(|seed| self.map_seed(seed))
     }
 
+    /// Lowest location for any seed in the seed ranges, found by mapping whole ranges at a time
+    fn min_range_location(&self) -> Option<u64> {
+        let seeds = self
+            .seeds
+            .iter()
+            .copied()
+            .tuple_pairs()
+            .map(|(start, length)| start..(start + length))
+            .collect::<Vec<_>>();
+        self.maps
+            .iter()
+            .fold(seeds, |ranges, map| {
+                ranges
+                    .into_iter()
+                    .flat_map(|range| map.map_range(range))
+                    .collect()
+            })
+            .into_iter()
+            .map(|range| range.start)
+            .min()
+    }
+
     fn map_seed_ranges(&self) -> impl Iterator<Item = u64> + '_ {
         self.seeds
             .iter()
@@ -170,5 +236,6 @@ mod test {
         assert_eq!(mapped, vec![82, 43, 86, 35]);
         let smallest_pt_2 = data.map_seed_ranges().min().unwrap();
         assert_eq!(smallest_pt_2, 46);
+        assert_eq!(data.min_range_location(), Some(46));
     }
 }
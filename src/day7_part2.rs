@@ -64,7 +64,7 @@ struct Hand {
 
 impl Hand {
     fn kind(&self) -> HandKind {
-        let mut cards = self.cards.clone();
+        let mut cards = self.cards;
         cards.sort();
 
         let mut matches = cards.iter().count_distinct().collect::<Vec<_>>();
@@ -131,9 +131,9 @@ impl FromStr for Hand {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (hand, bet) = s.split_once(' ').unwrap();
-        assert_eq!(hand.as_bytes().len(), 5);
+        assert_eq!(hand.len(), 5);
         let bytes: [u8; 5] = hand.as_bytes().try_into().unwrap();
-        let cards = bytes.map(|c| Card::from(c));
+        let cards = bytes.map(Card::from);
         Ok(Self {
             cards,
             bet: bet.parse()?,
@@ -163,6 +163,7 @@ fn part_2(lines: impl Iterator<Item = String>) -> String {
 // }
 
 #[cfg(test)]
+#[allow(clippy::redundant_closure, clippy::useless_vec)]
 mod test {
     use std::str::FromStr;
 
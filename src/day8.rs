@@ -1,84 +1,85 @@
 use std::collections::HashMap;
 
-use advent::advent_of_code;
+use advent::{advent_of_code, advent_parser};
 use advent_utils::*;
 
-#[advent_of_code(day = 8, part = 1)]
-fn part1(mut lines: impl Iterator<Item = String>) -> String {
-    let key = lines.next().unwrap();
+/// The directions to follow and where each node leads
+pub struct Network {
+    directions: String,
+    nodes: HashMap<String, (String, String)>,
+}
+
+#[advent_parser(day = 8)]
+fn parse(mut lines: impl Iterator<Item = String>) -> Result<Network, PuzzleError> {
+    let directions = lines.next().ok_or("missing directions")?;
     lines.next();
-    let map: HashMap<String, (String, String)> = lines
+    let nodes = lines
         .map(|line| {
-            let (key, values) = line.split_once("=").unwrap();
+            let (key, values) = line
+                .split_once('=')
+                .ok_or_else(|| format!("expected a node, got \"{line}\""))?;
             let (left, right) = values
                 .trim()
                 .trim_start_matches('(')
                 .trim_end_matches(')')
                 .split_once(',')
-                .unwrap();
-            (
+                .ok_or_else(|| format!("expected a pair of nodes, got \"{values}\""))?;
+            Ok::<_, String>((
                 key.trim().to_string(),
                 (left.trim().to_string(), right.trim().to_string()),
-            )
+            ))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
+    Ok(Network { directions, nodes })
+}
 
+#[advent_of_code(day = 8, part = 1)]
+fn part1(network: &Network) -> Result<usize, PuzzleError> {
+    let Network { directions, nodes } = network;
     let mut loops = 0;
     let mut current_node = "AAA";
     loop {
-        for c in key.bytes() {
-            let (left, right) = map.get(current_node).unwrap();
+        for c in directions.bytes() {
+            let (left, right) = nodes
+                .get(current_node)
+                .ok_or_else(|| format!("unknown node {current_node}"))?;
             if c == b'L' {
                 current_node = left;
             } else if c == b'R' {
                 current_node = right;
             } else {
-                panic!("unexpected character {c}");
+                return Err(format!("unexpected direction {}", c as char).into());
             }
             loops += 1;
             if current_node == "ZZZ" {
-                return loops.to_string();
+                return Ok(loops);
             }
         }
     }
 }
 
 #[advent_of_code(day = 8, part = 2)]
-fn part2(mut lines: impl Iterator<Item = String>) -> String {
-    let key = lines.next().unwrap();
-    lines.next();
-    let map: HashMap<String, (String, String)> = lines
-        .map(|line| {
-            let (key, values) = line.split_once("=").unwrap();
-            let (left, right) = values
-                .trim()
-                .trim_start_matches('(')
-                .trim_end_matches(')')
-                .split_once(',')
-                .unwrap();
-            (
-                key.trim().to_string(),
-                (left.trim().to_string(), right.trim().to_string()),
-            )
-        })
-        .collect();
-    let nodes: Vec<&str> = map
+fn part2(network: &Network) -> Result<usize, PuzzleError> {
+    let Network { directions, nodes } = network;
+    let starts: Vec<&str> = nodes
         .keys()
         .filter(|key| key.ends_with('A'))
         .map(|s| s.as_str())
         .collect();
-    let mut node_cycle = nodes.iter().map(|node| {
+    let mut node_cycle = starts.iter().map(|node| {
         let mut node = *node;
         let mut loops = 0usize;
         'outer: loop {
-            for c in key.bytes() {
-                let (left, right) = map.get(node).unwrap();
+            for c in directions.bytes() {
+                let (left, right) = nodes
+                    .get(node)
+                    .ok_or_else(|| format!("unknown node {node}"))?;
                 if c == b'L' {
                     node = left.as_str();
                 } else if c == b'R' {
                     node = right.as_str();
                 } else {
-                    panic!("unexpected character {c}");
+                    return Err(format!("unexpected direction {}", c as char));
                 }
 
                 loops += 1;
@@ -88,13 +89,13 @@ fn part2(mut lines: impl Iterator<Item = String>) -> String {
                 }
             }
         }
-        loops
+        Ok(loops)
     });
-    let mut l = node_cycle.next().unwrap();
+    let mut l = node_cycle.next().ok_or("no starting nodes")??;
     for value in node_cycle {
-        l = lcm(value, l);
+        l = lcm(value?, l);
     }
-    return l.to_string();
+    Ok(l)
 }
 
 fn lcm(l: usize, r: usize) -> usize {
@@ -111,6 +112,12 @@ fn lcm(l: usize, r: usize) -> usize {
 
 #[cfg(test)]
 mod test {
+    use super::Network;
+
+    fn parse(sample: &str) -> Network {
+        super::parse(sample.lines().map(|l| l.to_string())).unwrap()
+    }
+
     #[test]
     fn sample_data() {
         let sample = "RL
@@ -122,14 +129,14 @@ mod test {
         EEE = (EEE, EEE)
         GGG = (GGG, GGG)
         ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(super::part1(sample.lines().map(|l| l.to_string())), "2");
+        assert_eq!(super::part1(&parse(sample)).unwrap(), 2);
 
         let sample_2 = "LLR
 
         AAA = (BBB, BBB)
         BBB = (AAA, ZZZ)
         ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(super::part1(sample_2.lines().map(|l| l.to_string())), "6");
+        assert_eq!(super::part1(&parse(sample_2)).unwrap(), 6);
     }
 
     #[test]
@@ -144,7 +151,7 @@ mod test {
         22C = (22Z, 22Z)
         22Z = (22B, 22B)
         XXX = (XXX, XXX)";
-        assert_eq!(super::part2(two.lines().map(|l| l.to_string())), "6");
+        assert_eq!(super::part2(&parse(two)).unwrap(), 6);
     }
 
     #[test]
@@ -1,6 +1,34 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
 use clap::{Parser, Subcommand};
 
-use advent_utils::TestRunner;
+use advent_utils::{
+    parse_seconds, Answers, AocClient, BenchOptions, InputSource, PuzzleTimeout, ReportFormat,
+    RunOptions, TestRunner, Timeouts, DEFAULT_BASE_URL, DEFAULT_YEAR, STATE_DIR,
+};
+
+#[derive(clap::Args, Debug)]
+struct TimeoutArgs {
+    /// Seconds a puzzle may run before it is reported as timed out
+    #[arg(long, value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+    /// Timeout for a specific puzzle as day[:part]=seconds, can be repeated
+    #[arg(long)]
+    timeout_for: Vec<PuzzleTimeout>,
+}
+
+impl From<TimeoutArgs> for Timeouts {
+    fn from(args: TimeoutArgs) -> Self {
+        Timeouts {
+            global: args.timeout,
+            puzzles: args.timeout_for,
+        }
+    }
+}
 
 #[derive(Parser, Debug)]
 #[command()]
@@ -9,35 +37,404 @@ struct SingleTest {
     day: u8,
     #[arg(short, long)]
     part: u8,
+    /// Read the input from this file instead, or from stdin if given -
+    #[arg(short, long, conflicts_with = "example")]
+    input: Option<PathBuf>,
+    /// Use this string as the input instead
+    #[arg(short, long, conflicts_with = "profile")]
+    example: Option<String>,
+    /// Use this person's input from test_data/day_N/<profile>.txt
+    #[arg(long, conflicts_with = "input")]
+    profile: Option<String>,
+    /// Run against every profile and print a table of the answers
+    #[arg(long, conflicts_with_all = ["input", "example", "profile", "format"])]
+    all_profiles: bool,
+    /// Use this named solution instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+    /// One of text, json or junit
+    #[arg(short, long, default_value = "text")]
+    format: ReportFormat,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct AllTests {
+    /// Number of puzzles to run at the same time
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+    /// Use each person's input from test_data/day_N/<profile>.txt
+    #[arg(long)]
+    profile: Option<String>,
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+    /// One of text, json or junit
+    #[arg(short, long, default_value = "text")]
+    format: ReportFormat,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Verify {
+    /// Write the current answers to the file instead of checking them
+    #[arg(long)]
+    record: bool,
+    /// Defaults to ./answers.txt, or ./answers_<year>.txt for other years
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Bench {
+    /// Only benchmark this day, defaults to every day
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Only benchmark this part, defaults to both parts
+    #[arg(short, long)]
+    part: Option<u8>,
+    #[arg(short, long, default_value_t = 10)]
+    runs: usize,
+    #[arg(short, long, default_value_t = 2)]
+    warmup: usize,
+    /// Benchmark this named solution instead of the default one, skipping puzzles without it
+    #[arg(long, conflicts_with = "all_variants")]
+    variant: Option<String>,
+    /// Benchmark every solution of each puzzle instead of only the default one
+    #[arg(long)]
+    all_variants: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct ClientArgs {
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Minimum seconds between requests to the site
+    #[arg(long, value_parser = parse_seconds, default_value = "5")]
+    throttle: Duration,
+}
+
+impl ClientArgs {
+    fn client(self, year: u16) -> Result<AocClient, String> {
+        let state_dir = PathBuf::from(STATE_DIR);
+        Ok(AocClient {
+            base_url: self.base_url,
+            session: AocClient::load_session(&state_dir)?,
+            year,
+            throttle: self.throttle,
+            state_dir,
+        })
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Fetch {
+    /// Days to download, defaults to every registered day
+    #[arg(short, long)]
+    day: Vec<u8>,
+    /// Store the input as test_data/day_N/<profile>.txt
+    #[arg(long)]
+    profile: Option<String>,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Submit {
+    #[arg(short, long)]
+    day: u8,
+    #[arg(short, long)]
+    part: u8,
+    #[command(flatten)]
+    client: ClientArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct CrossCheck {
+    /// Only check this day, defaults to every day
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Only check this part, defaults to both parts
+    #[arg(short, long)]
+    part: Option<u8>,
+    /// Number of solutions to run at the same time
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Tui {
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Watch {
+    #[arg(short, long)]
+    day: u8,
+    #[arg(short, long)]
+    part: u8,
+    /// Use this person's input from test_data/day_N/<profile>.txt
+    #[arg(long)]
+    profile: Option<String>,
+    /// Use this named solution instead of the default one
+    #[arg(long)]
+    variant: Option<String>,
+    /// Seconds between checks for changed files
+    #[arg(long, value_parser = parse_seconds, default_value = "0.5")]
+    interval: Duration,
+    #[command(flatten)]
+    timeouts: TimeoutArgs,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct History {
+    /// Only show this day, defaults to every day
+    #[arg(short, long)]
+    day: Option<u8>,
+    /// Number of runs to show for each puzzle
+    #[arg(short, long, default_value_t = 10)]
+    limit: usize,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct Compare {
+    /// How many times slower than the median of earlier runs counts as a slowdown
+    #[arg(short, long, default_value_t = 1.5)]
+    threshold: f64,
+}
+
+#[derive(Parser, Debug)]
+#[command()]
+struct NewDay {
+    #[arg(short, long)]
+    day: u8,
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     Single(SingleTest),
-    All,
+    All(AllTests),
+    /// Checks every puzzle against the known answers
+    Verify(Verify),
+    /// Times puzzles over repeated runs
+    Bench(Bench),
+    /// Downloads puzzle inputs
+    Fetch(Fetch),
+    /// Solves a puzzle and submits the answer
+    Submit(Submit),
+    /// Creates the module and input file for a new day
+    New(NewDay),
+    /// Runs every variant of each puzzle and reports answers that disagree
+    CrossCheck(CrossCheck),
+    /// Re-runs a puzzle whenever its input or test_data/day_N.examples changes
+    Watch(Watch),
+    /// Shows recent runs of each puzzle
+    History(History),
+    /// Flags puzzles whose latest run got slower or changed answer
+    Compare(Compare),
+    /// Browse and run puzzles in an interactive calendar
+    Tui(Tui),
+    /// Lists registered puzzles and reports missing or duplicate ones
+    List,
 }
 
 #[derive(Parser, Debug)]
 #[command(author, version, about)]
 struct Args {
+    /// Which event's puzzles to use
+    #[arg(short, long, global = true, env = "AOC_YEAR", default_value_t = DEFAULT_YEAR)]
+    year: u16,
     #[command(subcommand)]
     command: Commands,
 }
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day7_part2;
-pub mod day8;
-
-fn main() {
-    let Args { command } = Args::parse();
-    match command {
-        Commands::Single(SingleTest { day, part }) => TestRunner::run_test(day, part),
-        Commands::All => TestRunner::run_all(),
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: advent_utils::CountingAllocator = advent_utils::CountingAllocator;
+
+// every src/day*.rs, found by build.rs
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+fn main() -> ExitCode {
+    let Args { year, command } = Args::parse();
+    let history = Path::new(STATE_DIR).join("history");
+    let passed = match command {
+        Commands::Single(SingleTest {
+            day,
+            part,
+            input,
+            example,
+            profile,
+            all_profiles,
+            variant,
+            timeouts,
+            format,
+        }) => {
+            let input = match (input, example, profile) {
+                (Some(path), _, _) => InputSource::from_path(path),
+                (None, Some(example), _) => InputSource::Inline(example),
+                (None, None, Some(profile)) => InputSource::Profile(profile),
+                (None, None, None) => InputSource::Default,
+            };
+            let options = RunOptions {
+                year,
+                timeouts: timeouts.into(),
+                format,
+                input,
+                variant,
+                history: Some(history),
+                ..Default::default()
+            };
+            if all_profiles {
+                TestRunner::run_profiles(day, part, &options)
+            } else {
+                TestRunner::run_test(day, part, &options)
+            }
+        }
+        Commands::All(AllTests {
+            jobs,
+            profile,
+            timeouts,
+            format,
+        }) => TestRunner::run_all(&RunOptions {
+            year,
+            jobs,
+            timeouts: timeouts.into(),
+            format,
+            input: profile.map_or(InputSource::Default, InputSource::Profile),
+            variant: None,
+            history: Some(history),
+        }),
+        Commands::Verify(Verify {
+            record,
+            file,
+            timeouts,
+        }) => {
+            let file = file.unwrap_or_else(|| Answers::default_path(year));
+            match TestRunner::verify(year, &file, record, &timeouts.into()) {
+                Ok(passed) => passed,
+                Err(e) => {
+                    eprintln!("unable to use answers file {}: {e}", file.display());
+                    false
+                }
+            }
+        }
+        Commands::Bench(Bench {
+            day,
+            part,
+            runs,
+            warmup,
+            variant,
+            all_variants,
+        }) => TestRunner::bench(&BenchOptions {
+            year,
+            day,
+            part,
+            runs,
+            warmup,
+            variant,
+            all_variants,
+        }),
+        Commands::Fetch(Fetch {
+            day,
+            profile,
+            client,
+        }) => match client.client(year) {
+            Ok(client) => {
+                let days = if day.is_empty() {
+                    TestRunner::days(year)
+                } else {
+                    day
+                };
+                TestRunner::fetch(&client, &days, profile.as_deref())
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        },
+        Commands::Submit(Submit { day, part, client }) => match client.client(year) {
+            Ok(client) => TestRunner::submit(&client, day, part),
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        },
+        Commands::New(NewDay { day }) => TestRunner::new_day(year, day),
+        Commands::CrossCheck(CrossCheck {
+            day,
+            part,
+            jobs,
+            timeouts,
+        }) => TestRunner::cross_check(year, day, part, jobs, &timeouts.into()),
+        Commands::Watch(Watch {
+            day,
+            part,
+            profile,
+            variant,
+            interval,
+            timeouts,
+        }) => TestRunner::watch(
+            day,
+            part,
+            &RunOptions {
+                year,
+                timeouts: timeouts.into(),
+                input: profile.map_or(InputSource::Default, InputSource::Profile),
+                variant,
+                ..Default::default()
+            },
+            interval,
+        ),
+        Commands::History(History { day, limit }) => {
+            match TestRunner::history(&history, year, day, limit) {
+                Ok(passed) => passed,
+                Err(e) => {
+                    eprintln!("unable to read history {}: {e}", history.display());
+                    false
+                }
+            }
+        }
+        Commands::Compare(Compare { threshold }) => {
+            match TestRunner::compare_history(&history, year, threshold) {
+                Ok(passed) => passed,
+                Err(e) => {
+                    eprintln!("unable to read history {}: {e}", history.display());
+                    false
+                }
+            }
+        }
+        Commands::Tui(Tui { timeouts }) => match TestRunner::tui(
+            year,
+            Answers::default_path(year),
+            Some(history),
+            timeouts.into(),
+        ) {
+            Ok(passed) => passed,
+            Err(e) => {
+                eprintln!("unable to run the dashboard: {e}");
+                false
+            }
+        },
+        Commands::List => TestRunner::list(year),
+    };
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
     }
 }